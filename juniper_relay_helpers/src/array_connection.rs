@@ -0,0 +1,379 @@
+use crate::connections::RelayConnection;
+use crate::cursor_errors::CursorError;
+use crate::{Cursor, OffsetCursor, PageInfo, PageRequest, RelayEdge};
+
+/// Builds a connection from all of the items in an in-memory array, applying the `first`, `after`,
+/// `last` and `before` of the page request.
+///
+/// Equivalent to `connectionFromArray` in graphql-relay-js. Edges get `OffsetCursor`s holding their
+/// position in the array, so the `after` and `before` of the request must be `OffsetCursor`s too.
+///
+/// ```
+/// use juniper::GraphQLObject;
+/// use juniper_relay_helpers::{connection_from_array, OffsetCursor, PageRequest, RelayConnection};
+///
+/// #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+/// pub struct Location {
+///     pub name: String,
+/// }
+///
+/// # fn locations() -> Result<(), juniper_relay_helpers::CursorError> {
+/// let locations = vec![
+///     Location { name: "Lumière".to_string() },
+///     Location { name: "Spring Meadows".to_string() },
+///     Location { name: "Flying Waters".to_string() },
+/// ];
+///
+/// let page_request = PageRequest::new(Some(2), Some(OffsetCursor::new(0, None)));
+/// let connection: LocationRelayConnection = connection_from_array(&locations, &page_request)?;
+///
+/// assert_eq!(connection.edges[0].node.name, "Spring Meadows");
+/// assert!(!connection.page_info.has_next_page);
+/// # Ok(())
+/// # }
+/// ```
+pub fn connection_from_array<C>(
+    nodes: &[C::NodeType],
+    page_request: &PageRequest,
+) -> Result<C, CursorError>
+where
+    C: RelayConnection,
+    C::NodeType: Clone,
+{
+    connection_from_array_slice(nodes, 0, nodes.len() as i32, page_request)
+}
+
+/// Builds a connection from a known sub-slice of a larger array, such as a page of rows loaded from
+/// the database with some room either side.
+///
+/// `slice_start` is the position of the first item of `slice` within the whole array, and
+/// `array_length` is the length of the whole array. Equivalent to `connectionFromArraySlice` in
+/// graphql-relay-js.
+///
+/// The page is picked out following the Relay spec's `ApplyCursorsToEdges` and `EdgesToReturn`
+/// algorithms, and `has_prev_page` / `has_next_page` follow `HasPreviousPage` / `HasNextPage`.
+/// As everything about the array is known, the optional parts of those are applied too: a page with an
+/// `after` cursor has a previous page, and a page with a `before` cursor has a next page, as long as
+/// there are items there.
+///
+/// Returns `CursorError::NegativePageSize` if `first` or `last` are negative, or the cursor's own
+/// error if `after` or `before` can't be decoded.
+pub fn connection_from_array_slice<C>(
+    slice: &[C::NodeType],
+    slice_start: i32,
+    array_length: i32,
+    page_request: &PageRequest,
+) -> Result<C, CursorError>
+where
+    C: RelayConnection,
+    C::NodeType: Clone,
+{
+    if page_request.first.is_some_and(|first| first < 0)
+        || page_request.last.is_some_and(|last| last < 0)
+    {
+        return Err(CursorError::NegativePageSize);
+    }
+    let after = page_request
+        .parsed_cursor::<OffsetCursor>()?
+        .map(|cursor| cursor.offset);
+    let before = page_request
+        .parsed_before_cursor::<OffsetCursor>()?
+        .map(|cursor| cursor.offset);
+
+    // ApplyCursorsToEdges:
+    let lower_bound = after.map_or(0, |after| after.saturating_add(1));
+    let upper_bound = before.unwrap_or(array_length);
+    let slice_end = slice_start.saturating_add(slice.len() as i32);
+    let mut start = slice_start.max(lower_bound);
+    let mut end = slice_end.min(upper_bound);
+
+    // EdgesToReturn:
+    if let Some(first) = page_request.first {
+        end = end.min(start.saturating_add(first));
+    }
+    if let Some(last) = page_request.last {
+        start = start.max(end.saturating_sub(last));
+    }
+
+    let to = end.saturating_sub(slice_start).clamp(0, slice.len() as i32) as usize;
+    let from = (start.saturating_sub(slice_start).max(0) as usize).min(to);
+    let page_size = page_request.first.or(page_request.last);
+    let cursors = (start..start + (to - from) as i32)
+        .map(|offset| OffsetCursor::new(offset, page_size).to_encoded_string())
+        .collect::<Vec<_>>();
+
+    let has_items = array_length > 0;
+    let page_info = PageInfo {
+        has_prev_page: (page_request.last.is_some() && start > lower_bound)
+            || after.is_some_and(|after| after >= 0 && has_items),
+        has_next_page: (page_request.first.is_some() && end < upper_bound)
+            || before.is_some_and(|before| before < array_length && has_items),
+        start_cursor: cursors.first().cloned(),
+        end_cursor: cursors.last().cloned(),
+    };
+
+    let edges = slice[from..to]
+        .iter()
+        .zip(cursors)
+        .map(|(node, cursor)| C::EdgeType::new_raw_cursor(node.clone(), Some(cursor)))
+        .collect();
+
+    Ok(C::from_edges(edges, Some(array_length), page_info))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorError, OffsetCursor, PageRequest, RelayConnection, connection_from_array,
+        connection_from_array_slice,
+    };
+    use juniper::GraphQLObject;
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    pub struct Letter {
+        value: String,
+    }
+
+    fn letters() -> Vec<Letter> {
+        ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|value| Letter {
+                value: value.to_string(),
+            })
+            .collect()
+    }
+
+    fn cursor(offset: i32) -> Option<OffsetCursor> {
+        Some(OffsetCursor::new(offset, None))
+    }
+
+    fn request(
+        first: Option<i32>,
+        after: Option<OffsetCursor>,
+        last: Option<i32>,
+        before: Option<OffsetCursor>,
+    ) -> PageRequest {
+        PageRequest::from_arguments(first, after, last, before)
+    }
+
+    fn page(request: &PageRequest) -> (Vec<String>, bool, bool) {
+        let conn: LetterRelayConnection = connection_from_array(&letters(), request).unwrap();
+        (
+            conn.edges.into_iter().map(|edge| edge.node.value).collect(),
+            conn.page_info.has_prev_page,
+            conn.page_info.has_next_page,
+        )
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_all_items() {
+        let conn: LetterRelayConnection =
+            connection_from_array(&letters(), &request(None, None, None, None)).unwrap();
+
+        assert_eq!(conn.count, 5);
+        assert_eq!(conn.edges.len(), 5);
+        assert_eq!(
+            conn.edges[2].cursor,
+            Some(OffsetCursor::new(2, None).to_encoded_string())
+        );
+        assert_eq!(
+            conn.page_info.start_cursor,
+            Some(OffsetCursor::new(0, None).to_encoded_string())
+        );
+        assert_eq!(
+            conn.page_info.end_cursor,
+            Some(OffsetCursor::new(4, None).to_encoded_string())
+        );
+        assert!(!conn.page_info.has_prev_page);
+        assert!(!conn.page_info.has_next_page);
+    }
+
+    #[test]
+    fn test_first() {
+        assert_eq!(
+            page(&request(Some(2), None, None, None)),
+            (values(&["A", "B"]), false, true)
+        );
+        assert_eq!(
+            page(&request(Some(5), None, None, None)),
+            (values(&["A", "B", "C", "D", "E"]), false, false)
+        );
+        assert_eq!(
+            page(&request(Some(10), None, None, None)),
+            (values(&["A", "B", "C", "D", "E"]), false, false)
+        );
+    }
+
+    #[test]
+    fn test_first_after() {
+        assert_eq!(
+            page(&request(Some(2), cursor(1), None, None)),
+            (values(&["C", "D"]), true, true)
+        );
+        assert_eq!(
+            page(&request(Some(10), cursor(1), None, None)),
+            (values(&["C", "D", "E"]), true, false)
+        );
+        assert_eq!(
+            page(&request(Some(2), cursor(4), None, None)),
+            (values(&[]), true, false)
+        );
+    }
+
+    #[test]
+    fn test_last() {
+        assert_eq!(
+            page(&request(None, None, Some(2), None)),
+            (values(&["D", "E"]), true, false)
+        );
+        assert_eq!(
+            page(&request(None, None, Some(10), None)),
+            (values(&["A", "B", "C", "D", "E"]), false, false)
+        );
+    }
+
+    #[test]
+    fn test_last_before() {
+        assert_eq!(
+            page(&request(None, None, Some(2), cursor(3))),
+            (values(&["B", "C"]), true, true)
+        );
+        assert_eq!(
+            page(&request(None, None, Some(10), cursor(3))),
+            (values(&["A", "B", "C"]), false, true)
+        );
+    }
+
+    #[test]
+    fn test_after_and_before() {
+        assert_eq!(
+            page(&request(None, cursor(0), None, cursor(4))),
+            (values(&["B", "C", "D"]), true, true)
+        );
+        assert_eq!(
+            page(&request(Some(2), cursor(0), None, cursor(4))),
+            (values(&["B", "C"]), true, true)
+        );
+        assert_eq!(
+            page(&request(None, cursor(0), Some(2), cursor(4))),
+            (values(&["C", "D"]), true, true)
+        );
+        assert_eq!(
+            page(&request(Some(3), cursor(0), Some(2), cursor(4))),
+            (values(&["C", "D"]), true, true)
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_cursors() {
+        assert_eq!(
+            page(&request(Some(2), cursor(-5), None, None)),
+            (values(&["A", "B"]), false, true)
+        );
+        assert_eq!(
+            page(&request(None, None, Some(2), cursor(10))),
+            (values(&["D", "E"]), true, false)
+        );
+        assert_eq!(
+            page(&request(None, cursor(3), None, cursor(1))),
+            (values(&[]), true, true)
+        );
+    }
+
+    #[test]
+    fn test_extreme_cursor_offsets() {
+        let all = letters();
+
+        let conn: LetterRelayConnection = connection_from_array_slice(
+            &all[1..4],
+            1,
+            5,
+            &request(None, None, Some(2), cursor(i32::MIN)),
+        )
+        .unwrap();
+        assert!(conn.edges.is_empty());
+        assert_eq!(conn.page_info.start_cursor, None);
+
+        let conn: LetterRelayConnection = connection_from_array_slice(
+            &all[1..4],
+            1,
+            5,
+            &request(Some(2), cursor(i32::MAX), None, None),
+        )
+        .unwrap();
+        assert!(conn.edges.is_empty());
+        assert!(conn.page_info.has_prev_page);
+
+        assert_eq!(
+            page(&request(Some(2), cursor(i32::MIN), None, cursor(i32::MAX))),
+            (values(&["A", "B"]), false, true)
+        );
+    }
+
+    #[test]
+    fn test_zero_and_negative_sizes() {
+        assert_eq!(
+            page(&request(Some(0), None, None, None)),
+            (values(&[]), false, true)
+        );
+
+        let result: Result<LetterRelayConnection, _> =
+            connection_from_array(&letters(), &request(Some(-1), None, None, None));
+        assert_eq!(result.err(), Some(CursorError::NegativePageSize));
+
+        let result: Result<LetterRelayConnection, _> =
+            connection_from_array(&letters(), &request(None, None, Some(-1), None));
+        assert_eq!(result.err(), Some(CursorError::NegativePageSize));
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        let mut request = request(Some(2), None, None, None);
+        request.after = Some("not-a-cursor".to_string());
+
+        let result: Result<LetterRelayConnection, _> = connection_from_array(&letters(), &request);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_slice() {
+        let all = letters();
+
+        // Only B, C and D have been loaded:
+        let conn: LetterRelayConnection =
+            connection_from_array_slice(&all[1..4], 1, 5, &request(Some(2), cursor(1), None, None))
+                .unwrap();
+        assert_eq!(conn.count, 5);
+        assert_eq!(
+            conn.edges
+                .iter()
+                .map(|edge| edge.node.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["C", "D"]
+        );
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(OffsetCursor::new(2, Some(2)).to_encoded_string())
+        );
+        assert!(conn.page_info.has_prev_page);
+        assert!(conn.page_info.has_next_page);
+
+        // The page runs off the end of the slice, so is cut short:
+        let conn: LetterRelayConnection =
+            connection_from_array_slice(&all[1..3], 1, 5, &request(Some(3), cursor(1), None, None))
+                .unwrap();
+        assert_eq!(conn.edges.len(), 1);
+        assert_eq!(conn.edges[0].node.value, "C");
+
+        // The page is entirely outside of the slice:
+        let conn: LetterRelayConnection =
+            connection_from_array_slice(&all[1..3], 1, 5, &request(Some(1), cursor(3), None, None))
+                .unwrap();
+        assert!(conn.edges.is_empty());
+        assert_eq!(conn.page_info.start_cursor, None);
+    }
+}
@@ -0,0 +1,265 @@
+use juniper::{LookAheadSelection, ScalarValue};
+
+/// Which of the parts of a connection that take work to build a query has actually asked for.
+///
+/// Build it from the look-ahead of the connection field and pass it to
+/// `RelayConnection::new_for_selection`, so the total count is only fetched, edge cursors only encoded
+/// and the `PageInfo` only worked out when they're part of the query:
+///
+/// ```nocompile
+/// async fn characters(
+///     first: Option<i32>,
+///     after: Option<StringCursor>,
+///     executor: &Executor<'_, '_, Context>,
+/// ) -> FieldResult<CharacterRelayConnection> {
+///     let selection = ConnectionSelection::from_look_ahead(&executor.look_ahead());
+///     ...
+///     Ok(CharacterRelayConnection::new_for_selection(
+///         &nodes,
+///         || count_characters(),
+///         NodeCursorProvider::new(|character: &Character| StringCursor::new(character.id.to_string())),
+///         Some(PageRequest::new(first, after)),
+///         selection,
+///     ))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ConnectionSelection {
+    /// Whether `count` is selected.
+    pub count: bool,
+
+    /// Whether `cursor` is selected on the edges.
+    pub edge_cursors: bool,
+
+    /// Whether `pageInfo` is selected.
+    pub page_info: bool,
+}
+
+impl ConnectionSelection {
+    /// A selection of every field, for when there's no look-ahead to go on.
+    pub fn all() -> Self {
+        ConnectionSelection {
+            count: true,
+            edge_cursors: true,
+            page_info: true,
+        }
+    }
+
+    /// Works out the selection from the look-ahead of the connection field, ie
+    /// `executor.look_ahead()` within the resolver that returns the connection. Aliased fields and
+    /// fields within fragments are included.
+    pub fn from_look_ahead<S: ScalarValue>(connection: &LookAheadSelection<'_, S>) -> Self {
+        let edges = selected_fields(connection, "edges");
+        ConnectionSelection {
+            count: !selected_fields(connection, "count").is_empty(),
+            edge_cursors: edges
+                .iter()
+                .any(|edges| !selected_fields(edges, "cursor").is_empty()),
+            page_info: !selected_fields(connection, "pageInfo").is_empty(),
+        }
+    }
+}
+
+/// The children of a selection for the given field, whether or not they've been aliased.
+fn selected_fields<'a, S: ScalarValue>(
+    selection: &LookAheadSelection<'a, S>,
+    name: &str,
+) -> Vec<LookAheadSelection<'a, S>> {
+    selection
+        .children()
+        .iter()
+        .filter(|child| child.field_original_name() == name)
+        .copied()
+        .collect()
+}
+
+impl Default for ConnectionSelection {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ConnectionSelection, Cursor, NodeCursorProvider, OffsetCursor, OffsetCursorProvider,
+        PageRequest, RelayConnection, StringCursor,
+    };
+    use juniper::{
+        DefaultScalarValue, EmptyMutation, EmptySubscription, Executor, GraphQLObject, RootNode,
+        ScalarValue, Variables, graphql_object, graphql_value,
+    };
+    use std::sync::Mutex;
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    pub struct Expedition {
+        name: String,
+    }
+
+    #[derive(Default)]
+    struct Context {
+        selections: Mutex<Vec<ConnectionSelection>>,
+        counts: Mutex<i32>,
+    }
+    impl juniper::Context for Context {}
+
+    struct Query;
+
+    #[graphql_object(context = Context, scalar = DefaultScalarValue)]
+    impl Query {
+        fn expeditions(
+            first: Option<i32>,
+            executor: &Executor<'_, '_, Context>,
+        ) -> ExpeditionRelayConnection {
+            let ctx = executor.context();
+            let selection = ConnectionSelection::from_look_ahead(&executor.look_ahead());
+            ctx.selections.lock().unwrap().push(selection);
+
+            let nodes = ["33", "34"].map(|name| Expedition {
+                name: name.to_string(),
+            });
+            ExpeditionRelayConnection::new_for_selection(
+                &nodes,
+                || {
+                    *ctx.counts.lock().unwrap() += 1;
+                    2
+                },
+                NodeCursorProvider::new(|e: &Expedition| StringCursor::new(e.name.clone())),
+                Some(PageRequest::new(first, None::<StringCursor>)),
+                selection,
+            )
+        }
+
+        fn latest_expeditions(
+            last: Option<i32>,
+            executor: &Executor<'_, '_, Context>,
+        ) -> ExpeditionRelayConnection {
+            let ctx = executor.context();
+            let selection = ConnectionSelection::from_look_ahead(&executor.look_ahead());
+            ctx.selections.lock().unwrap().push(selection);
+
+            let nodes = ["32", "33"].map(|name| Expedition {
+                name: name.to_string(),
+            });
+            ExpeditionRelayConnection::new_for_selection(
+                &nodes,
+                || {
+                    *ctx.counts.lock().unwrap() += 1;
+                    10
+                },
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new_backward(last, None::<OffsetCursor>)),
+                selection,
+            )
+        }
+    }
+
+    type Schema = RootNode<Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    fn run(query: &str) -> (juniper::Value, ConnectionSelection, i32) {
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let ctx = Context::default();
+        let (value, errors) =
+            juniper::execute_sync(query, None, &schema, &Variables::new(), &ctx).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+
+        let selection = ctx.selections.lock().unwrap()[0];
+        let counts = *ctx.counts.lock().unwrap();
+        (value, selection, counts)
+    }
+
+    #[test]
+    fn test_nodes_only() {
+        let (value, selection, counts) = run("{ expeditions { edges { node { name } } } }");
+        assert_eq!(
+            selection,
+            ConnectionSelection {
+                count: false,
+                edge_cursors: false,
+                page_info: false,
+            }
+        );
+        assert_eq!(counts, 0);
+        assert_eq!(
+            value,
+            graphql_value!({
+                "expeditions": {
+                    "edges": [{ "node": { "name": "33" } }, { "node": { "name": "34" } }]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_everything() {
+        let (value, selection, counts) = run(
+            "{ expeditions(first: 2) { count edges { cursor } pageInfo { hasNextPage endCursor } } }",
+        );
+        assert_eq!(selection, ConnectionSelection::all());
+        assert_eq!(counts, 1);
+
+        let end_cursor = StringCursor::new("34".to_string()).to_encoded_string();
+        assert_eq!(
+            value,
+            graphql_value!({
+                "expeditions": {
+                    "count": 2,
+                    "edges": [
+                        { "cursor": (StringCursor::new("33".to_string()).to_encoded_string()) },
+                        { "cursor": (end_cursor.clone()) },
+                    ],
+                    "pageInfo": { "hasNextPage": false, "endCursor": end_cursor },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_aliases_and_fragments() {
+        let (_, selection, counts) = run("{ expeditions { total: count ...EdgeFields } }
+            fragment EdgeFields on ExpeditionConnection { edges { c: cursor } }");
+        assert_eq!(
+            selection,
+            ConnectionSelection {
+                count: true,
+                edge_cursors: true,
+                page_info: false,
+            }
+        );
+        assert_eq!(counts, 1);
+    }
+
+    #[test]
+    fn test_page_info_without_count_still_counts() {
+        let (_, selection, counts) = run("{ expeditions(first: 1) { pageInfo { hasNextPage } } }");
+        assert!(selection.page_info && !selection.count);
+        assert_eq!(counts, 1);
+    }
+
+    #[test]
+    fn test_cursors_counting_back_from_the_end_fetch_the_total() {
+        let (value, selection, counts) = run("{ latestExpeditions(last: 2) { edges { cursor } } }");
+        assert!(selection.edge_cursors && !selection.count && !selection.page_info);
+        assert_eq!(counts, 1);
+
+        let offsets = value
+            .as_object_value()
+            .and_then(|query| query.get_field_value("latestExpeditions"))
+            .and_then(|connection| connection.as_object_value())
+            .and_then(|connection| connection.get_field_value("edges"))
+            .and_then(|edges| edges.as_list_value())
+            .unwrap()
+            .iter()
+            .map(|edge| {
+                let cursor = edge
+                    .as_object_value()
+                    .and_then(|edge| edge.get_field_value("cursor"))
+                    .and_then(|cursor| cursor.as_scalar())
+                    .and_then(|cursor| cursor.try_as_str())
+                    .unwrap();
+                OffsetCursor::from_encoded_string(cursor).unwrap().offset
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, vec![8, 9]);
+    }
+}
@@ -1,35 +1,377 @@
-use crate::RelayEdge;
-use crate::cursor_provider::CursorProvider;
+use crate::cursor_provider::{CursorProvider, PaginationMetadata};
+use crate::{
+    ConnectionSelection, Cursor, CursorError, PageInfo, PageRequest, RelayEdge, RelayEdgeParts,
+};
 
 /// Common trait for Relay connections. Will be implemented by the codegen.
 pub trait RelayConnection {
     /// The type of the Edge - this will be added for you in the codegen.
-    type EdgeType: RelayEdge;
+    type EdgeType: RelayEdge<NodeType = Self::NodeType>;
 
     /// The underlying type of Node we're Connection-ing. Will be filled in for you by the codegen.
     type NodeType;
 
     /// Builds a connection and associated edges from a Vec of the Nodes themselves. Pagination cursors
     /// can also be generated for you by providing the page info and CursorProvider trait instance.
+    ///
+    /// Each node is cloned into its edge. Use `from_nodes` to move owned nodes in instead.
     fn new(
         nodes: &[Self::NodeType],
         total_items: i32,
-        cursor_provider: impl CursorProvider,
-        page_request: Option<crate::PageRequest>,
+        cursor_provider: impl CursorProvider<Self::NodeType>,
+        page_request: Option<PageRequest>,
+    ) -> Self
+    where
+        Self: Sized,
+        Self::NodeType: Clone,
+    {
+        Self::from_nodes(
+            nodes.iter().cloned(),
+            total_items,
+            cursor_provider,
+            page_request,
+        )
+    }
+
+    /// Builds a connection the same way as `new`, but takes the nodes by value so that they're moved
+    /// into the edges rather than cloned. The node type doesn't need to implement `Clone`.
+    fn from_nodes(
+        nodes: impl IntoIterator<Item = Self::NodeType>,
+        total_items: i32,
+        cursor_provider: impl CursorProvider<Self::NodeType>,
+        page_request: Option<PageRequest>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let nodes = nodes.into_iter().collect::<Vec<_>>();
+        let metadata = PaginationMetadata {
+            total_count: Some(total_items),
+            page_request,
+            has_more: None,
+        };
+        let page_info = cursor_provider.get_page_info(&metadata, &nodes);
+        let edges = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                let cursor = cursor_provider
+                    .get_cursor_for_item(&metadata, idx as i32, &node)
+                    .to_encoded_string();
+                Self::EdgeType::new_raw_cursor(node, Some(cursor))
+            })
+            .collect();
+        Self::from_edges(edges, Some(total_items), page_info)
+    }
+
+    /// Builds a connection, only doing the work for the parts of it the query has selected. The total
+    /// count is only fetched when `count` or `pageInfo` are selected, or the cursor provider
+    /// `requires_total_count` for the cursors being built. Edge cursors are only encoded when `cursor`
+    /// is selected (or the edges `REQUIRES_CURSOR`) and the `PageInfo` is only worked out when
+    /// `pageInfo` is selected. Parts that aren't selected are left empty.
+    ///
+    /// Get the selection from `ConnectionSelection::from_look_ahead`.
+    fn new_for_selection(
+        nodes: &[Self::NodeType],
+        total_items: impl FnOnce() -> i32,
+        cursor_provider: impl CursorProvider<Self::NodeType>,
+        page_request: Option<PageRequest>,
+        selection: ConnectionSelection,
+    ) -> Self
+    where
+        Self: Sized,
+        Self::NodeType: Clone,
+    {
+        let edge_cursors = selection.edge_cursors || Self::EdgeType::REQUIRES_CURSOR;
+        let cursors_need_total = (edge_cursors || selection.page_info)
+            && cursor_provider.requires_total_count(page_request.as_ref());
+        let total_count =
+            (selection.count || selection.page_info || cursors_need_total).then(total_items);
+        let metadata = PaginationMetadata {
+            total_count,
+            page_request,
+            has_more: None,
+        };
+        let edges = nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                let cursor = edge_cursors.then(|| {
+                    cursor_provider
+                        .get_cursor_for_item(&metadata, idx as i32, node)
+                        .to_encoded_string()
+                });
+                Self::EdgeType::new_raw_cursor(node.clone(), cursor)
+            })
+            .collect();
+        let page_info = if selection.page_info {
+            cursor_provider.get_page_info(&metadata, nodes)
+        } else {
+            PageInfo::default()
+        };
+        Self::from_edges(edges, total_count.filter(|_| selection.count), page_info)
+    }
+
+    /// Builds a connection from edges and page info that have already been worked out, such as by
+    /// `connection_from_array`. `total_items` is the `count` of the connection, if it's known.
+    fn from_edges(
+        edges: Vec<Self::EdgeType>,
+        total_items: Option<i32>,
+        page_info: PageInfo,
     ) -> Self;
+
+    /// Converts the connection into one of another node type, such as from the rows loaded from the
+    /// database into the objects exposed in the schema. The page info, `count` and edge cursors are
+    /// kept as they are. Extra `connection_fields` of the new connection are left as their defaults.
+    ///
+    /// Edges without a node, which connections with `resilient` nullability can have, are dropped.
+    /// The page info and `count` aren't worked out again for the edges that are left, so
+    /// `startCursor` and `endCursor` can still point at a dropped edge.
+    ///
+    /// Connections whose edges `REQUIRES_CURSOR`, such as those with `strict` nullability, can only be
+    /// converted into with `try_map_nodes`, which fails if an edge doesn't have a cursor. Using
+    /// `map_nodes` for them fails to build.
+    ///
+    /// ```nocompile
+    /// let characters: CharacterRelayConnection = rows.map_nodes(Character::from);
+    /// ```
+    fn map_nodes<C, F>(self, mut f: F) -> C
+    where
+        Self: RelayConnectionParts + Sized,
+        Self::EdgeType: RelayEdgeParts,
+        C: RelayConnection,
+        F: FnMut(Self::NodeType) -> C::NodeType,
+    {
+        const {
+            assert!(
+                !C::EdgeType::REQUIRES_CURSOR,
+                "edges that require a cursor can only be mapped into with `try_map_nodes`"
+            )
+        };
+        let (edges, total_items, page_info) = self.into_parts();
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| match edge.into_parts() {
+                (Some(node), cursor) => Some(C::EdgeType::new_raw_cursor(f(node), cursor)),
+                (None, _) => None,
+            })
+            .collect();
+        C::from_edges(edges, total_items, page_info)
+    }
+
+    /// Converts the connection the same way as `map_nodes`, stopping at the first node that fails to
+    /// convert and returning its error. As with `map_nodes`, edges without a node are dropped and the
+    /// page info and `count` are kept as they are.
+    ///
+    /// Converting into a connection whose edges `REQUIRES_CURSOR` fails with
+    /// `CursorError::MissingCursor` if any of the edges don't have a cursor.
+    fn try_map_nodes<C, E, F>(self, mut f: F) -> Result<C, E>
+    where
+        Self: RelayConnectionParts + Sized,
+        Self::EdgeType: RelayEdgeParts,
+        C: RelayConnection,
+        E: From<CursorError>,
+        F: FnMut(Self::NodeType) -> Result<C::NodeType, E>,
+    {
+        let (edges, total_items, page_info) = self.into_parts();
+        let edges = edges
+            .into_iter()
+            .filter_map(|edge| {
+                let (node, cursor) = edge.into_parts();
+                node.map(|node| (node, cursor))
+            })
+            .map(|(node, cursor)| Ok(C::EdgeType::try_new_raw_cursor(f(node)?, cursor)?))
+            .collect::<Result<Vec<_>, E>>()?;
+        Ok(C::from_edges(edges, total_items, page_info))
+    }
+
+    /// Converts the connection the same way as `map_nodes`, with an async function. Nodes are
+    /// converted one at a time, in order. As with `map_nodes`, it can't convert into a connection
+    /// whose edges `REQUIRES_CURSOR`.
+    ///
+    /// ```nocompile
+    /// let characters: CharacterRelayConnection = rows
+    ///     .map_nodes_async(|row| async move { Character::load_details(row, &ctx.db).await })
+    ///     .await;
+    /// ```
+    fn map_nodes_async<C, F, Fut>(self, mut f: F) -> impl Future<Output = C>
+    where
+        Self: RelayConnectionParts + Sized,
+        Self::EdgeType: RelayEdgeParts,
+        C: RelayConnection,
+        F: FnMut(Self::NodeType) -> Fut,
+        Fut: Future<Output = C::NodeType>,
+    {
+        const {
+            assert!(
+                !C::EdgeType::REQUIRES_CURSOR,
+                "edges that require a cursor can only be mapped into with `try_map_nodes`"
+            )
+        };
+        async move {
+            let (edges, total_items, page_info) = self.into_parts();
+            let mut mapped = Vec::with_capacity(edges.len());
+            for edge in edges {
+                if let (Some(node), cursor) = edge.into_parts() {
+                    mapped.push(C::EdgeType::new_raw_cursor(f(node).await, cursor));
+                }
+            }
+            C::from_edges(mapped, total_items, page_info)
+        }
+    }
+}
+
+/// Takes a connection apart, so that it can be converted with `RelayConnection::map_nodes`. Implemented
+/// by the codegen alongside `RelayConnection`.
+pub trait RelayConnectionParts: RelayConnection {
+    /// Takes the connection apart into its edges, `count` and page info, the reverse of `from_edges`.
+    /// Edges that are missing from a connection with `resilient` nullability are skipped.
+    fn into_parts(self) -> (Vec<Self::EdgeType>, Option<i32>, PageInfo);
+}
+
+/// Connections whose `count` can be left empty, so that they can be built without a total count.
+/// Implemented by the codegen for connections with `#[relay(nullable_count)]`.
+pub trait NullableCountConnection: RelayConnection {
+    /// Builds a connection without needing a total count, from a page that was fetched with one more
+    /// node than was asked for. Saves running a `COUNT(*)` just to work out whether there's a next page.
+    ///
+    /// Fetch up to `first + 1` nodes (or `last + 1` when paginating backwards, keeping them in order so
+    /// the extra node is the first one). The extra node is trimmed off, and whether it was there is
+    /// used for `has_next_page` (or `has_prev_page`). The `count` of the connection is left empty.
+    ///
+    /// Fails with `CursorError::TotalCountRequired` if the cursor provider needs the total count for
+    /// the page, such as `OffsetCursorProvider` with `last` and no `before`.
+    fn new_overfetched(
+        nodes: &[Self::NodeType],
+        cursor_provider: impl CursorProvider<Self::NodeType>,
+        page_request: PageRequest,
+    ) -> Result<Self, CursorError>
+    where
+        Self: Sized,
+        Self::NodeType: Clone,
+    {
+        if cursor_provider.requires_total_count(Some(&page_request)) {
+            return Err(CursorError::TotalCountRequired);
+        }
+
+        let (page, has_more) = trim_overfetched_nodes(nodes, &page_request);
+        let metadata = PaginationMetadata {
+            total_count: None,
+            page_request: Some(page_request),
+            has_more: Some(has_more),
+        };
+        let edges = page
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| {
+                Self::EdgeType::new(
+                    node.clone(),
+                    cursor_provider.get_cursor_for_item(&metadata, idx as i32, node),
+                )
+            })
+            .collect();
+        let page_info = cursor_provider.get_page_info(&metadata, page);
+        Ok(Self::from_edges(edges, None, page_info))
+    }
+}
+
+/// Builds a connection straight from an iterator of nodes, moving them into the edges:
+///
+/// ```nocompile
+/// let connection: CharacterRelayConnection = load_characters()
+///     .into_iter()
+///     .filter(|character| character.is_playable)
+///     .into_connection(total, OffsetCursorProvider::new(), Some(page_request));
+/// ```
+pub trait IntoConnection: IntoIterator + Sized {
+    /// Builds the connection with `RelayConnection::from_nodes`.
+    fn into_connection<C>(
+        self,
+        total_items: i32,
+        cursor_provider: impl CursorProvider<Self::Item>,
+        page_request: Option<PageRequest>,
+    ) -> C
+    where
+        C: RelayConnection<NodeType = Self::Item>,
+    {
+        C::from_nodes(self, total_items, cursor_provider, page_request)
+    }
+}
+
+impl<I: IntoIterator> IntoConnection for I {}
+
+/// Trims the extra node off an overfetched page, returning the page and whether there was one.
+fn trim_overfetched_nodes<'a, N>(nodes: &'a [N], page_request: &PageRequest) -> (&'a [N], bool) {
+    let len = nodes.len();
+    match (page_request.first, page_request.last) {
+        (Some(first), _) if len > first.max(0) as usize => (&nodes[..first.max(0) as usize], true),
+        (None, Some(last)) if len > last.max(0) as usize => {
+            (&nodes[len - last.max(0) as usize..], true)
+        }
+        _ => (nodes, false),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{OffsetCursor, PageInfo};
-    use juniper::GraphQLObject;
-    use juniper_relay_helpers_codegen::RelayConnection;
+    use crate::{
+        ConnectionSelection, Cursor, CursorError, IntoConnection, NodeCursorProvider,
+        NullableCountConnection, OffsetCursor, OffsetCursorProvider, PageInfo, PageRequest,
+        RelayConnection, RelayEdge, StringCursor,
+    };
+    use juniper::{FieldError, GraphQLObject};
 
     #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
     pub struct User {
         name: String,
     }
 
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(nullable_count)]
+    pub struct Visitor {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(nullability = "strict")]
+    pub struct StrictUser {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(nullability = "resilient")]
+    pub struct ResilientUser {
+        name: String,
+    }
+
+    #[derive(Debug, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(node = User)]
+    pub struct Membership {
+        role: String,
+    }
+
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    #[relay(connection_fields(
+        /// How many of the messages haven't been read.
+        unread_count: i32,
+    ))]
+    pub struct Message {
+        body: String,
+    }
+
+    // Can't be cloned, so needs to be moved into the connection:
+    #[derive(Debug, GraphQLObject, RelayConnection)]
+    #[relay(derive(Debug))]
+    pub struct Canvas {
+        painting: String,
+    }
+
+    // Private nodes get private Connection and Edge types:
+    #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+    struct Secret {
+        value: String,
+    }
+
     #[test]
     fn connection_types_are_generated() {
         let conn = UserRelayConnection {
@@ -82,4 +424,482 @@ mod tests {
         assert_eq!(edge2.node.name, "Sciel");
         assert_eq!(edge2.cursor, Some("some-cursor".into()));
     }
+
+    #[test]
+    fn connection_new_with_node_cursor_provider() {
+        let users = vec![
+            User {
+                name: "Lune".to_owned(),
+            },
+            User {
+                name: "Sciel".to_owned(),
+            },
+        ];
+
+        let conn = UserRelayConnection::new(
+            &users,
+            5,
+            NodeCursorProvider::new(|user: &User| StringCursor::new(user.name.clone())),
+            Some(PageRequest::new(Some(2), None::<StringCursor>)),
+        );
+
+        assert_eq!(conn.count, 5);
+        assert_eq!(
+            conn.edges[1].cursor,
+            Some(StringCursor::new("Sciel".to_owned()).to_encoded_string())
+        );
+        assert_eq!(
+            conn.page_info.end_cursor,
+            Some(StringCursor::new("Sciel".to_owned()).to_encoded_string())
+        );
+        assert!(conn.page_info.has_next_page);
+        assert!(!conn.page_info.has_prev_page);
+    }
+
+    #[test]
+    fn connection_new_overfetched() {
+        let visitors = ["Lune", "Sciel", "Maelle"]
+            .iter()
+            .map(|name| Visitor {
+                name: name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let provider =
+            NodeCursorProvider::new(|visitor: &Visitor| StringCursor::new(visitor.name.clone()));
+
+        // Asked for two, got the extra one, so there's a next page:
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            &provider,
+            PageRequest::new(Some(2), None::<StringCursor>),
+        )
+        .unwrap();
+        assert_eq!(conn.count, None);
+        assert_eq!(conn.edges.len(), 2);
+        assert_eq!(conn.edges[1].node.name, "Sciel");
+        assert_eq!(
+            conn.page_info.end_cursor,
+            Some(StringCursor::new("Sciel".to_owned()).to_encoded_string())
+        );
+        assert!(conn.page_info.has_next_page);
+        assert!(!conn.page_info.has_prev_page);
+
+        // Asked for three, and that's all there is:
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            &provider,
+            PageRequest::new(Some(3), None::<StringCursor>),
+        )
+        .unwrap();
+        assert_eq!(conn.edges.len(), 3);
+        assert!(!conn.page_info.has_next_page);
+
+        // Backwards, the extra node comes first:
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            &provider,
+            PageRequest::new_backward(Some(2), None::<StringCursor>),
+        )
+        .unwrap();
+        assert_eq!(conn.edges[0].node.name, "Sciel");
+        assert_eq!(conn.edges[1].node.name, "Maelle");
+        assert!(conn.page_info.has_prev_page);
+        assert!(!conn.page_info.has_next_page);
+    }
+
+    #[test]
+    fn connection_new_overfetched_offsets() {
+        let visitors = ["Lune", "Sciel", "Maelle"]
+            .iter()
+            .map(|name| Visitor {
+                name: name.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            OffsetCursorProvider::new(),
+            PageRequest::new(Some(2), Some(OffsetCursor::new(9, Some(2)))),
+        )
+        .unwrap();
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(OffsetCursor::new(10, Some(2)).to_encoded_string())
+        );
+        assert!(conn.page_info.has_prev_page);
+        assert!(conn.page_info.has_next_page);
+
+        // Offsets for `last` count back from the end of the result set, which isn't known:
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            OffsetCursorProvider::new(),
+            PageRequest::new_backward(Some(2), None::<OffsetCursor>),
+        );
+        assert_eq!(conn.err(), Some(CursorError::TotalCountRequired));
+    }
+
+    #[test]
+    fn connection_new_overfetched_nullable_count() {
+        let visitors = ["Lune", "Sciel"]
+            .iter()
+            .map(|name| Visitor {
+                name: name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let provider =
+            NodeCursorProvider::new(|visitor: &Visitor| StringCursor::new(visitor.name.clone()));
+
+        let conn = VisitorRelayConnection::new_overfetched(
+            &visitors,
+            &provider,
+            PageRequest::new(Some(1), None::<StringCursor>),
+        )
+        .unwrap();
+        assert_eq!(conn.count, None);
+        assert_eq!(conn.edges.len(), 1);
+        assert!(conn.page_info.has_next_page);
+
+        let conn = VisitorRelayConnection::new(&visitors, 2, &provider, None);
+        assert_eq!(conn.count, Some(2));
+    }
+
+    #[test]
+    fn connection_new_with_nullability_profiles() {
+        let provider =
+            NodeCursorProvider::new(|user: &StrictUser| StringCursor::new(user.name.clone()));
+        let conn = StrictUserRelayConnection::new(
+            &[StrictUser {
+                name: "Lune".to_owned(),
+            }],
+            1,
+            provider,
+            None,
+        );
+        assert_eq!(conn.edges[0].node.name, "Lune");
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(StringCursor::new("Lune".to_owned()).to_encoded_string())
+        );
+
+        let provider =
+            NodeCursorProvider::new(|user: &ResilientUser| StringCursor::new(user.name.clone()));
+        let conn = ResilientUserRelayConnection::new(
+            &[ResilientUser {
+                name: "Sciel".to_owned(),
+            }],
+            1,
+            provider,
+            None,
+        );
+        let edges = conn.edges.unwrap();
+        let edge = edges[0].as_ref().unwrap();
+        assert_eq!(edge.node.as_ref().unwrap().name, "Sciel");
+        assert_eq!(
+            edge.cursor,
+            Some(StringCursor::new("Sciel".to_owned()).to_encoded_string())
+        );
+    }
+
+    #[test]
+    fn connection_new_with_edge_data() {
+        let members = vec![(
+            User {
+                name: "Lune".to_owned(),
+            },
+            Membership {
+                role: "Mage".to_owned(),
+            },
+        )];
+
+        let conn = MembershipRelayConnection::new(
+            &members,
+            1,
+            NodeCursorProvider::new(|(user, _): &(User, Membership)| {
+                StringCursor::new(user.name.clone())
+            }),
+            None,
+        );
+        assert_eq!(conn.edges[0].node.name, "Lune");
+        assert_eq!(conn.edges[0].role, "Mage");
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(StringCursor::new("Lune".to_owned()).to_encoded_string())
+        );
+    }
+
+    #[test]
+    fn connection_new_with_connection_fields() {
+        let messages = vec![Message {
+            body: "Tomorrow comes".to_owned(),
+        }];
+
+        let conn = MessageRelayConnection {
+            unread_count: 3,
+            ..MessageRelayConnection::new(&messages, 1, OffsetCursorProvider::new(), None)
+        };
+        assert_eq!(conn.unread_count, 3);
+        assert_eq!(conn.edges.len(), 1);
+
+        let conn = MessageRelayConnection::from_edges(vec![], None, PageInfo::default());
+        assert_eq!(conn.unread_count, 0);
+    }
+
+    #[test]
+    fn connection_for_private_node() {
+        let secrets = vec![Secret {
+            value: "Renoir".to_owned(),
+        }];
+
+        let conn = SecretRelayConnection::new(&secrets, 1, OffsetCursorProvider::new(), None);
+        assert_eq!(conn.edges[0].node.value, "Renoir");
+    }
+
+    // Written by hand, so only implements the required parts of `RelayConnection`:
+    struct Guestbook {
+        edges: Vec<UserRelayEdge>,
+    }
+
+    impl RelayConnection for Guestbook {
+        type EdgeType = UserRelayEdge;
+        type NodeType = User;
+
+        fn from_edges(edges: Vec<UserRelayEdge>, _: Option<i32>, _: PageInfo) -> Self {
+            Guestbook { edges }
+        }
+    }
+
+    #[test]
+    fn hand_written_connection() {
+        let guestbook = Guestbook::new(
+            &[User {
+                name: "Esquie".to_owned(),
+            }],
+            1,
+            OffsetCursorProvider::new(),
+            None,
+        );
+        assert_eq!(guestbook.edges[0].node.name, "Esquie");
+    }
+
+    #[test]
+    fn connection_from_owned_nodes() {
+        let canvases = vec![
+            Canvas {
+                painting: "The Monolith".to_owned(),
+            },
+            Canvas {
+                painting: "Old Lumière".to_owned(),
+            },
+        ];
+
+        let conn = CanvasRelayConnection::from_nodes(
+            canvases,
+            3,
+            NodeCursorProvider::new(|canvas: &Canvas| StringCursor::new(canvas.painting.clone())),
+            Some(PageRequest::new(Some(2), None::<StringCursor>)),
+        );
+        assert_eq!(conn.count, 3);
+        assert_eq!(conn.edges[1].node.painting, "Old Lumière");
+        assert_eq!(
+            conn.edges[1].cursor,
+            Some(StringCursor::new("Old Lumière".to_owned()).to_encoded_string())
+        );
+        assert_eq!(conn.page_info.end_cursor, conn.edges[1].cursor);
+        assert!(conn.page_info.has_next_page);
+    }
+
+    #[test]
+    fn into_connection() {
+        let conn: CanvasRelayConnection = ["The Monolith", "Old Lumière", "Visages"]
+            .into_iter()
+            .filter(|painting| painting.starts_with('V'))
+            .map(|painting| Canvas {
+                painting: painting.to_owned(),
+            })
+            .into_connection(
+                1,
+                OffsetCursorProvider::new(),
+                Some(PageRequest::new(Some(1), None::<OffsetCursor>)),
+            );
+        assert_eq!(conn.edges.len(), 1);
+        assert_eq!(conn.edges[0].node.painting, "Visages");
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(OffsetCursor::new(0, None).to_encoded_string())
+        );
+        assert!(!conn.page_info.has_next_page);
+    }
+
+    fn users(names: &[&str]) -> UserRelayConnection {
+        let users = names
+            .iter()
+            .map(|name| User {
+                name: name.to_string(),
+            })
+            .collect::<Vec<_>>();
+        UserRelayConnection::new(
+            &users,
+            10,
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(Some(2), None::<OffsetCursor>)),
+        )
+    }
+
+    #[test]
+    fn map_nodes() {
+        let users = users(&["Gustave", "Maelle"]);
+        let conn: CanvasRelayConnection = users.clone().map_nodes(|user| Canvas {
+            painting: format!("Portrait of {}", user.name),
+        });
+
+        assert_eq!(conn.count, 10);
+        assert_eq!(conn.page_info, users.page_info);
+        assert_eq!(conn.edges[1].node.painting, "Portrait of Maelle");
+        assert_eq!(conn.edges[1].cursor, users.edges[1].cursor);
+    }
+
+    #[test]
+    fn map_nodes_between_nullability_profiles() {
+        let mut resilient: ResilientUserRelayConnection =
+            users(&["Lune", "Sciel", "Monoco"]).map_nodes(|user| ResilientUser { name: user.name });
+        let edges = resilient.edges.as_mut().unwrap();
+        edges[1] = None;
+        edges[2].as_mut().unwrap().node = None;
+
+        let strict: StrictUserRelayConnection = resilient
+            .try_map_nodes(|user| Ok::<_, CursorError>(StrictUser { name: user.name }))
+            .unwrap();
+        assert_eq!(strict.edges.len(), 1);
+        assert_eq!(strict.edges[0].node.name, "Lune");
+        assert_eq!(
+            strict.edges[0].cursor,
+            Some(OffsetCursor::new(0, None).to_encoded_string())
+        );
+    }
+
+    #[test]
+    fn strict_edges_always_get_cursors() {
+        let provider =
+            NodeCursorProvider::new(|user: &StrictUser| StringCursor::new(user.name.clone()));
+        let conn = StrictUserRelayConnection::new_for_selection(
+            &[StrictUser {
+                name: "Lune".to_owned(),
+            }],
+            || 1,
+            &provider,
+            None,
+            ConnectionSelection {
+                count: false,
+                edge_cursors: false,
+                page_info: false,
+            },
+        );
+        assert_eq!(
+            conn.edges[0].cursor,
+            Some(StringCursor::new("Lune".to_owned()).to_encoded_string())
+        );
+    }
+
+    #[test]
+    fn strict_edges_from_raw_cursors() {
+        let lune = || StrictUser {
+            name: "Lune".to_owned(),
+        };
+        assert_eq!(
+            StrictUserRelayEdge::try_new_raw_cursor(lune(), None).err(),
+            Some(CursorError::MissingCursor)
+        );
+
+        let edge =
+            StrictUserRelayEdge::try_new_raw_cursor(lune(), Some("lune".to_owned())).unwrap();
+        assert_eq!(edge.cursor, Some("lune".to_owned()));
+    }
+
+    #[test]
+    fn strict_edges_without_cursor() {
+        let users = UserRelayConnection::from_edges(
+            vec![UserRelayEdge::new_raw_cursor(
+                User {
+                    name: "Lune".to_owned(),
+                },
+                None,
+            )],
+            None,
+            PageInfo::default(),
+        );
+        let strict: Result<StrictUserRelayConnection, CursorError> =
+            users.try_map_nodes(|user| Ok(StrictUser { name: user.name }));
+        assert_eq!(strict.err(), Some(CursorError::MissingCursor));
+    }
+
+    #[test]
+    fn map_nodes_with_edge_data() {
+        let members: MembershipRelayConnection = users(&["Verso"]).map_nodes(|user| {
+            (
+                user,
+                Membership {
+                    role: "Painter".to_owned(),
+                },
+            )
+        });
+        assert_eq!(members.edges[0].role, "Painter");
+
+        let roles: UserRelayConnection = members.map_nodes(|(user, membership)| User {
+            name: format!("{} the {}", user.name, membership.role),
+        });
+        assert_eq!(roles.edges[0].node.name, "Verso the Painter");
+    }
+
+    #[test]
+    fn try_map_nodes() {
+        let result: Result<MessageRelayConnection, FieldError> = users(&["Esquie", "Gustave"])
+            .try_map_nodes(|user| match user.name.as_str() {
+                "Esquie" => Ok(Message {
+                    body: "Whee!".to_owned(),
+                }),
+                name => Err(format!("{name} has nothing to say").into()),
+            });
+        assert_eq!(
+            result.err().map(|e| e.message().to_owned()),
+            Some("Gustave has nothing to say".to_owned())
+        );
+
+        let conn: MessageRelayConnection = users(&["Esquie"])
+            .try_map_nodes(|user| {
+                Ok::<_, FieldError>(Message {
+                    body: format!("{} says hi", user.name),
+                })
+            })
+            .unwrap();
+        assert_eq!(conn.edges[0].node.body, "Esquie says hi");
+        assert_eq!(conn.unread_count, 0);
+    }
+
+    #[test]
+    fn map_nodes_async() {
+        let users = users(&["Lune", "Sciel"]);
+        let conn: CanvasRelayConnection =
+            block_on(users.clone().map_nodes_async(|user| async move {
+                Canvas {
+                    painting: user.name.to_uppercase(),
+                }
+            }));
+
+        assert_eq!(conn.count, 10);
+        assert_eq!(conn.page_info, users.page_info);
+        assert_eq!(conn.edges[0].node.painting, "LUNE");
+        assert_eq!(conn.edges[1].node.painting, "SCIEL");
+        assert_eq!(conn.edges[1].cursor, users.edges[1].cursor);
+    }
+
+    /// Runs a future that never has to wait, without pulling in an async runtime.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        loop {
+            if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
 }
@@ -0,0 +1,390 @@
+use crate::Cursor;
+use crate::cursor_encryption::{CursorCipher, global_cursor_cipher};
+use crate::cursor_errors::CursorError;
+use crate::cursor_signing::{CursorSigner, global_cursor_signer};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::prelude::*;
+use std::sync::{Arc, OnceLock};
+
+static CURSOR_CODEC: OnceLock<CursorCodec> = OnceLock::new();
+
+static DEFAULT_CURSOR_CODEC: CursorCodec = CursorCodec::new();
+
+/// Unpadded engines accept padded input too, so switching to them doesn't break the cursors and
+/// identifiers clients already hold.
+const URL_SAFE_NO_PAD_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+const STANDARD_NO_PAD_LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// How the bytes of a cursor are turned into the string handed to clients.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum TextEncoding {
+    /// Base64 with the url safe alphabet and `=` padding. This is the default.
+    #[default]
+    UrlSafe,
+
+    /// Base64 with the url safe alphabet and no padding, for when something between the client and
+    /// the server strips `=` from query strings. Padded input is still accepted.
+    UrlSafeNoPad,
+
+    /// Base64 with the standard alphabet and `=` padding.
+    Standard,
+
+    /// Base64 with the standard alphabet and no padding. Padded input is still accepted.
+    StandardNoPad,
+}
+
+impl TextEncoding {
+    /// Encodes bytes into a string.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            TextEncoding::UrlSafe => BASE64_URL_SAFE.encode(bytes),
+            TextEncoding::UrlSafeNoPad => URL_SAFE_NO_PAD_LENIENT.encode(bytes),
+            TextEncoding::Standard => BASE64_STANDARD.encode(bytes),
+            TextEncoding::StandardNoPad => STANDARD_NO_PAD_LENIENT.encode(bytes),
+        }
+    }
+
+    /// Decodes a string produced by `encode` back into bytes.
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, base64::DecodeError> {
+        match self {
+            TextEncoding::UrlSafe => BASE64_URL_SAFE.decode(input),
+            TextEncoding::UrlSafeNoPad => URL_SAFE_NO_PAD_LENIENT.decode(input),
+            TextEncoding::Standard => BASE64_STANDARD.decode(input),
+            TextEncoding::StandardNoPad => STANDARD_NO_PAD_LENIENT.decode(input),
+        }
+    }
+}
+
+/// Compresses the bytes of a cursor before they're protected and encoded. Implement this over your
+/// compression library of choice and add it to a codec with `CursorCodec::with_compression`.
+pub trait CursorCompression: Send + Sync {
+    /// Compresses the raw bytes of a cursor.
+    fn compress(&self, bytes: &[u8]) -> Vec<u8>;
+
+    /// Reverses `compress`. Return `CursorError::InvalidCursorEncoding` if the bytes can't be
+    /// decompressed.
+    fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, CursorError>;
+}
+
+/// Turns the raw string of a cursor into the string handed to clients, and back again.
+///
+/// Encoding runs each step in turn, and decoding runs them in reverse:
+///
+/// 1. the cursor is serialized with `Cursor::to_versioned_raw_string`
+/// 2. the bytes are compressed, if the codec has a `CursorCompression`
+/// 3. the bytes are encrypted, if the codec has a `CursorCipher`
+/// 4. the bytes are signed, if the codec has a `CursorSigner`
+/// 5. the bytes are encoded to text with the codec's `TextEncoding`
+///
+/// A signer or cipher installed with `set_cursor_signer` or `set_cursor_cipher` is used by every
+/// codec that doesn't have its own.
+///
+/// Install a codec for the whole process with `set_cursor_codec`, or override `Cursor::codec` to use
+/// a different one for a single cursor type:
+///
+/// ```
+/// use juniper_relay_helpers::{
+///     set_cursor_codec, Cursor, CursorCodec, CursorSigner, CursorSigningKey, OffsetCursor,
+///     TextEncoding,
+/// };
+///
+/// # fn startup() {
+/// let codec = CursorCodec::new()
+///     .with_text_encoding(TextEncoding::UrlSafeNoPad)
+///     .with_signer(CursorSigner::new(CursorSigningKey::new("2025-05", "secret-from-your-config")));
+/// set_cursor_codec(codec).expect("cursor codec already installed");
+///
+/// // No trailing `=` to get lost in query strings:
+/// let encoded = OffsetCursor::new(10, Some(10)).to_encoded_string();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CursorCodec {
+    text_encoding: TextEncoding,
+    compression: Option<Arc<dyn CursorCompression>>,
+    signer: Option<Arc<CursorSigner>>,
+    cipher: Option<Arc<CursorCipher>>,
+}
+
+impl CursorCodec {
+    /// Creates a codec that only base64 encodes cursors with the url safe alphabet, plus any
+    /// globally installed signer and cipher. This matches how cursors are encoded by default.
+    pub const fn new() -> Self {
+        CursorCodec {
+            text_encoding: TextEncoding::UrlSafe,
+            compression: None,
+            signer: None,
+            cipher: None,
+        }
+    }
+
+    /// Sets how the final bytes are encoded to text.
+    pub const fn with_text_encoding(mut self, text_encoding: TextEncoding) -> Self {
+        self.text_encoding = text_encoding;
+        self
+    }
+
+    /// Compresses cursors before they're protected and encoded.
+    pub fn with_compression(mut self, compression: impl CursorCompression + 'static) -> Self {
+        self.compression = Some(Arc::new(compression));
+        self
+    }
+
+    /// Signs cursors with this signer, instead of any globally installed one.
+    pub fn with_signer(mut self, signer: CursorSigner) -> Self {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Encrypts cursors with this cipher, instead of any globally installed one.
+    pub fn with_cipher(mut self, cipher: CursorCipher) -> Self {
+        self.cipher = Some(Arc::new(cipher));
+        self
+    }
+
+    /// The text encoding used by the codec.
+    pub fn text_encoding(&self) -> TextEncoding {
+        self.text_encoding
+    }
+
+    /// Encodes the raw string of a cursor.
+    pub fn encode(&self, raw: &str) -> String {
+        let mut bytes = raw.as_bytes().to_vec();
+        if let Some(compression) = &self.compression {
+            bytes = compression.compress(&bytes);
+        }
+        if let Some(cipher) = self.cipher() {
+            bytes = cipher.encrypt_bytes(&bytes);
+        }
+        if let Some(signer) = self.signer() {
+            bytes = signer.sign_bytes(&bytes);
+        }
+        self.text_encoding.encode(&bytes)
+    }
+
+    /// Reverses `encode`, returning the raw string of the cursor.
+    pub fn decode(&self, input: &str) -> Result<String, CursorError> {
+        let mut bytes = self.text_encoding.decode(input)?;
+        if let Some(signer) = self.signer() {
+            bytes = signer.verify_bytes(&bytes)?;
+        }
+        if let Some(cipher) = self.cipher() {
+            bytes = cipher.decrypt_bytes(&bytes)?;
+        }
+        if let Some(compression) = &self.compression {
+            bytes = compression.decompress(&bytes)?;
+        }
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Encodes a cursor with this codec, regardless of the codec the cursor type uses.
+    pub fn encode_cursor<C: Cursor + ?Sized>(&self, cursor: &C) -> String {
+        self.encode(&cursor.to_versioned_raw_string())
+    }
+
+    /// Decodes a cursor string produced by `encode_cursor` into the cursor type.
+    pub fn decode_cursor<C: Cursor + ?Sized>(
+        &self,
+        input: &str,
+    ) -> Result<C::CursorType, CursorError> {
+        C::from_raw_string(&self.decode(input)?)
+    }
+
+    fn signer(&self) -> Option<&CursorSigner> {
+        self.signer.as_deref().or(global_cursor_signer())
+    }
+
+    fn cipher(&self) -> Option<&CursorCipher> {
+        self.cipher.as_deref().or(global_cursor_cipher())
+    }
+}
+
+impl Default for CursorCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for CursorCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorCodec")
+            .field("text_encoding", &self.text_encoding)
+            .field("compression", &self.compression.is_some())
+            .field("signer", &self.signer)
+            .field("cipher", &self.cipher)
+            .finish()
+    }
+}
+
+/// Installs the codec that every cursor is encoded and decoded with, unless its type overrides
+/// `Cursor::codec`. Call this once at startup, before any cursors are built.
+///
+/// The codec is process-global rather than per schema: it's held in a single static, so every schema
+/// in the process shares it. `RelayIdentifier`s aren't affected by it.
+///
+/// Only one codec can be installed; if one already is, the codec passed in is handed back.
+pub fn set_cursor_codec(codec: CursorCodec) -> Result<(), CursorCodec> {
+    CURSOR_CODEC.set(codec)
+}
+
+/// The codec installed with `set_cursor_codec`, or the default codec if there isn't one.
+pub fn global_cursor_codec() -> &'static CursorCodec {
+    CURSOR_CODEC.get().unwrap_or(&DEFAULT_CURSOR_CODEC)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorCipher, CursorCodec, CursorCompression, CursorEncryptionKey, CursorError,
+        CursorSigner, CursorSigningKey, OffsetCursor, RelayCursor, StringCursor, TextEncoding,
+    };
+
+    /// Collapses runs of the same byte into `[count, byte]` pairs.
+    struct RunLength;
+
+    impl CursorCompression for RunLength {
+        fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+            let mut out = vec![];
+            for chunk in bytes.chunk_by(|a, b| a == b) {
+                for run in chunk.chunks(u8::MAX as usize) {
+                    out.extend_from_slice(&[run.len() as u8, run[0]]);
+                }
+            }
+            out
+        }
+
+        fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, CursorError> {
+            if !bytes.len().is_multiple_of(2) {
+                return Err(CursorError::InvalidCursorEncoding);
+            }
+            Ok(bytes
+                .chunks(2)
+                .flat_map(|pair| std::iter::repeat_n(pair[1], pair[0] as usize))
+                .collect())
+        }
+    }
+
+    static UNPADDED: CursorCodec =
+        CursorCodec::new().with_text_encoding(TextEncoding::UrlSafeNoPad);
+
+    #[derive(Debug, RelayCursor, Eq, PartialEq)]
+    #[cursor(tag = "page", codec = UNPADDED)]
+    struct PageCursor(u32);
+
+    #[test]
+    fn test_text_encodings() {
+        let bytes = b"offset:1:10>?";
+        assert_eq!(TextEncoding::UrlSafe.encode(bytes), "b2Zmc2V0OjE6MTA-Pw==");
+        assert_eq!(
+            TextEncoding::UrlSafeNoPad.encode(bytes),
+            "b2Zmc2V0OjE6MTA-Pw"
+        );
+        assert_eq!(TextEncoding::Standard.encode(bytes), "b2Zmc2V0OjE6MTA+Pw==");
+        assert_eq!(
+            TextEncoding::StandardNoPad.encode(bytes),
+            "b2Zmc2V0OjE6MTA+Pw"
+        );
+
+        for encoding in [
+            TextEncoding::UrlSafe,
+            TextEncoding::UrlSafeNoPad,
+            TextEncoding::Standard,
+            TextEncoding::StandardNoPad,
+        ] {
+            assert_eq!(encoding.decode(&encoding.encode(bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_unpadded_accepts_padded() {
+        assert_eq!(
+            TextEncoding::UrlSafeNoPad
+                .decode("b2Zmc2V0OjE6MTA-Pw==")
+                .unwrap(),
+            b"offset:1:10>?"
+        );
+        assert_eq!(
+            TextEncoding::StandardNoPad
+                .decode("b2Zmc2V0OjE6MTA+Pw==")
+                .unwrap(),
+            b"offset:1:10>?"
+        );
+        assert!(TextEncoding::UrlSafe.decode("b2Zmc2V0OjE6MTA-Pw").is_err());
+    }
+
+    #[test]
+    fn test_default_codec_matches_plain_encoding() {
+        let cursor = OffsetCursor::new(1, Some(10));
+        assert_eq!(
+            CursorCodec::new().encode_cursor(&cursor),
+            "b2Zmc2V0OjE6MTA="
+        );
+        assert_eq!(cursor.to_encoded_string(), "b2Zmc2V0OjE6MTA=");
+    }
+
+    #[test]
+    fn test_compression() {
+        let codec = CursorCodec::new().with_compression(RunLength);
+        let cursor = StringCursor::new("a".repeat(300));
+
+        let encoded = codec.encode_cursor(&cursor);
+        assert!(encoded.len() < cursor.to_encoded_string().len());
+        assert_eq!(
+            codec.decode_cursor::<StringCursor>(&encoded).unwrap().value,
+            "a".repeat(300)
+        );
+    }
+
+    #[test]
+    fn test_full_pipeline() {
+        let codec = CursorCodec::new()
+            .with_text_encoding(TextEncoding::StandardNoPad)
+            .with_compression(RunLength)
+            .with_cipher(CursorCipher::new(CursorEncryptionKey::new("enc", [7; 32])))
+            .with_signer(CursorSigner::new(CursorSigningKey::new("sig", "secret")));
+
+        let encoded = codec.encode_cursor(&OffsetCursor::new(100, Some(20)));
+        assert!(!encoded.ends_with('='));
+
+        let decoded = codec.decode_cursor::<OffsetCursor>(&encoded).unwrap();
+        assert_eq!(decoded.offset, 100);
+        assert_eq!(decoded.first, Some(20));
+
+        // Plain cursors don't get through:
+        let plain = CursorCodec::new()
+            .with_text_encoding(TextEncoding::StandardNoPad)
+            .encode_cursor(&OffsetCursor::new(100, Some(20)));
+        assert_eq!(
+            codec.decode_cursor::<OffsetCursor>(&plain).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_per_type_codec() {
+        let encoded = PageCursor(12).to_encoded_string();
+        assert_eq!(encoded, "cGFnZToxMg");
+        assert_eq!(
+            PageCursor::from_encoded_string(&encoded),
+            Ok(PageCursor(12))
+        );
+
+        // Cursors issued with padding are still accepted:
+        assert_eq!(
+            PageCursor::from_encoded_string("cGFnZToxMg=="),
+            Ok(PageCursor(12))
+        );
+    }
+}
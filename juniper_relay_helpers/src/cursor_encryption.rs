@@ -0,0 +1,306 @@
+use crate::Cursor;
+use crate::cursor_errors::CursorError;
+use crate::cursor_signing::{key_id_header, split_key_id_header};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use std::sync::OnceLock;
+
+/// Length in bytes of the random nonce stored in an encrypted cursor.
+const NONCE_LEN: usize = 24;
+
+static CURSOR_CIPHER: OnceLock<CursorCipher> = OnceLock::new();
+
+/// A 256-bit secret key used to encrypt cursors, along with an identifier so the right key can be
+/// found again when decrypting. The identifier is stored in the cursor in the clear, so don't put
+/// anything sensitive in it.
+#[derive(Clone)]
+pub struct CursorEncryptionKey {
+    id: String,
+    key: [u8; 32],
+}
+
+impl CursorEncryptionKey {
+    /// Builds a key from its identifier and 32 bytes of random key material.
+    ///
+    /// Panics if the identifier is longer than 255 bytes.
+    pub fn new(id: impl Into<String>, key: [u8; 32]) -> Self {
+        let id = id.into();
+        assert!(
+            id.len() <= u8::MAX as usize,
+            "cursor key ids must be at most 255 bytes"
+        );
+        CursorEncryptionKey { id, key }
+    }
+
+    /// The identifier of the key.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.key.into())
+    }
+}
+
+impl std::fmt::Debug for CursorEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorEncryptionKey")
+            .field("id", &self.id)
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Encrypts cursors with XChaCha20-Poly1305 so that clients can neither read nor alter them.
+///
+/// Works with any `Cursor` implementation: the raw string from `Cursor::to_raw_string` is encrypted
+/// before being encoded. As with `CursorSigner`, new cursors are encrypted with the encryption key
+/// and cursors encrypted with any of the decryption keys are still accepted, so keys can be rotated:
+///
+/// ```
+/// use juniper_relay_helpers::{CursorCipher, CursorEncryptionKey, StringCursor};
+///
+/// let cipher = CursorCipher::new(CursorEncryptionKey::new("2025-05", [7; 32]))
+///     .with_decryption_key(CursorEncryptionKey::new("2025-04", [3; 32]));
+///
+/// let encoded = cipher.encrypt(&StringCursor::new("internal-token".to_string()));
+/// let decoded = cipher.decrypt::<StringCursor>(&encoded).unwrap();
+/// ```
+///
+/// Install it once at startup with `set_cursor_cipher` to have every cursor encrypted and decrypted
+/// as part of `Cursor::to_encoded_string` and `Cursor::from_encoded_string`.
+#[derive(Debug, Clone)]
+pub struct CursorCipher {
+    encryption_key: CursorEncryptionKey,
+    decryption_keys: Vec<CursorEncryptionKey>,
+}
+
+impl CursorCipher {
+    /// Creates a cipher that encrypts and decrypts with the given key.
+    pub fn new(encryption_key: CursorEncryptionKey) -> Self {
+        CursorCipher {
+            encryption_key,
+            decryption_keys: vec![],
+        }
+    }
+
+    /// Adds a key that cursors will still be decrypted with, but that new cursors won't be
+    /// encrypted with.
+    pub fn with_decryption_key(mut self, key: CursorEncryptionKey) -> Self {
+        self.decryption_keys.push(key);
+        self
+    }
+
+    /// Encrypts the bytes of a cursor. The encrypted bytes are the length of the key id, the key id,
+    /// a random nonce and then the ciphertext. The key id is authenticated along with the ciphertext.
+    pub fn encrypt_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let header = key_id_header(&self.encryption_key.id);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .encryption_key
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: bytes,
+                    aad: &header,
+                },
+            )
+            .expect("encrypting into a Vec can't run out of space");
+
+        let mut encrypted = header;
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        encrypted
+    }
+
+    /// Decrypts bytes produced by `encrypt_bytes`. Returns `CursorError::CursorDecryptionFailed` if the
+    /// key is unknown, the key is wrong, or the bytes have been corrupted or tampered with.
+    pub fn decrypt_bytes(&self, encrypted: &[u8]) -> Result<Vec<u8>, CursorError> {
+        let (id, rest) =
+            split_key_id_header(encrypted).ok_or(CursorError::CursorDecryptionFailed)?;
+        if rest.len() < NONCE_LEN {
+            return Err(CursorError::CursorDecryptionFailed);
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = std::iter::once(&self.encryption_key)
+            .chain(self.decryption_keys.iter())
+            .find(|key| key.id.as_bytes() == id)
+            .ok_or(CursorError::CursorDecryptionFailed)?;
+
+        key.cipher()
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &encrypted[..1 + id.len()],
+                },
+            )
+            .map_err(|_| CursorError::CursorDecryptionFailed)
+    }
+
+    /// Encrypts a cursor, returning the string to hand to clients encoded with the text encoding of
+    /// the cursor's codec.
+    pub fn encrypt<C: Cursor + ?Sized>(&self, cursor: &C) -> String {
+        C::codec()
+            .text_encoding()
+            .encode(&self.encrypt_bytes(cursor.to_versioned_raw_string().as_bytes()))
+    }
+
+    /// Decrypts an encrypted cursor string and decodes it into the cursor type.
+    pub fn decrypt<C: Cursor + ?Sized>(&self, input: &str) -> Result<C::CursorType, CursorError> {
+        let decoded = C::codec().text_encoding().decode(input)?;
+        let raw = String::from_utf8(self.decrypt_bytes(&decoded)?)?;
+        C::from_raw_string(&raw)
+    }
+}
+
+/// Installs a cipher that every cursor will be encrypted and decrypted with from then on. Call this
+/// once at startup, before any cursors are built.
+///
+/// Only one cipher can be installed; if one already is, the cipher passed in is handed back.
+pub fn set_cursor_cipher(cipher: CursorCipher) -> Result<(), CursorCipher> {
+    CURSOR_CIPHER.set(cipher)
+}
+
+/// The cipher installed with `set_cursor_cipher`, if any.
+pub(crate) fn global_cursor_cipher() -> Option<&'static CursorCipher> {
+    CURSOR_CIPHER.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorCipher, CursorCodec, CursorEncryptionKey, CursorError, OffsetCursor,
+        RelayCursor, StringCursor, TextEncoding,
+    };
+    use base64::prelude::*;
+
+    static STANDARD: CursorCodec = CursorCodec::new().with_text_encoding(TextEncoding::Standard);
+
+    #[derive(Debug, RelayCursor, Eq, PartialEq)]
+    #[cursor(tag = "page", codec = STANDARD)]
+    struct PageCursor(u32);
+
+    fn cipher() -> CursorCipher {
+        CursorCipher::new(CursorEncryptionKey::new("current", [7; 32]))
+    }
+
+    #[test]
+    fn test_encrypt_and_decrypt() {
+        let cipher = cipher();
+        let encoded = cipher.encrypt(&StringCursor::new("internal-token".to_string()));
+
+        let decoded = cipher.decrypt::<StringCursor>(&encoded).unwrap();
+        assert_eq!(decoded.value, "internal-token");
+    }
+
+    #[test]
+    fn test_encrypted_cursor_is_not_readable() {
+        let encoded = cipher().encrypt(&StringCursor::new("internal-token".to_string()));
+        let bytes = BASE64_URL_SAFE.decode(encoded).unwrap();
+        assert!(
+            !bytes
+                .windows("internal-token".len())
+                .any(|w| w == b"internal-token")
+        );
+    }
+
+    #[test]
+    fn test_encrypt_uses_cursor_text_encoding() {
+        let cipher = cipher();
+        let encoded = cipher.encrypt(&PageCursor(12));
+
+        let bytes = TextEncoding::Standard.decode(&encoded).unwrap();
+        assert_eq!(cipher.decrypt_bytes(&bytes).unwrap(), b"page:12");
+        assert_eq!(cipher.decrypt::<PageCursor>(&encoded), Ok(PageCursor(12)));
+    }
+
+    #[test]
+    fn test_nonce_is_random() {
+        let cursor = OffsetCursor::new(10, Some(5));
+        assert_ne!(cipher().encrypt(&cursor), cipher().encrypt(&cursor));
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let encoded = cipher().encrypt(&OffsetCursor::new(10, Some(5)));
+
+        // Same key id, different key:
+        let other = CursorCipher::new(CursorEncryptionKey::new("current", [8; 32]));
+        assert_eq!(
+            other.decrypt::<OffsetCursor>(&encoded).err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+
+        // Unknown key id:
+        let other = CursorCipher::new(CursorEncryptionKey::new("other", [7; 32]));
+        assert_eq!(
+            other.decrypt::<OffsetCursor>(&encoded).err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn test_corrupted_cursor_rejected() {
+        let cipher = cipher();
+        let encoded = cipher.encrypt(&OffsetCursor::new(10, Some(5)));
+
+        let mut bytes = BASE64_URL_SAFE.decode(&encoded).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        assert_eq!(
+            cipher
+                .decrypt::<OffsetCursor>(&BASE64_URL_SAFE.encode(&bytes))
+                .err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+
+        // Changing the key id header also fails, even to a key we know:
+        let mut bytes = BASE64_URL_SAFE.decode(&encoded).unwrap();
+        bytes[1] = b'C';
+        let renamed = CursorCipher::new(CursorEncryptionKey::new("Current", [7; 32]));
+        assert_eq!(
+            renamed
+                .decrypt::<OffsetCursor>(&BASE64_URL_SAFE.encode(&bytes))
+                .err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+
+        // Truncated:
+        assert_eq!(
+            cipher.decrypt_bytes(&[7, b'c', b'u']).err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+
+        // Plain cursors aren't accepted:
+        let plain = OffsetCursor::new(10, Some(5)).to_encoded_string();
+        assert!(cipher.decrypt::<OffsetCursor>(&plain).is_err());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old_cipher = CursorCipher::new(CursorEncryptionKey::new("old", [1; 32]));
+        let old_cursor = old_cipher.encrypt(&OffsetCursor::new(3, None));
+
+        let rotated = cipher().with_decryption_key(CursorEncryptionKey::new("old", [1; 32]));
+        assert_eq!(
+            rotated.decrypt::<OffsetCursor>(&old_cursor).unwrap().offset,
+            3
+        );
+
+        let new_cursor = rotated.encrypt(&OffsetCursor::new(3, None));
+        assert_eq!(
+            old_cipher.decrypt::<OffsetCursor>(&new_cursor).err(),
+            Some(CursorError::CursorDecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn test_key_debug_redacts_key() {
+        let key = CursorEncryptionKey::new("current", [7; 32]);
+        assert_eq!(key.id(), "current");
+        assert!(format!("{:?}", key).contains("<redacted>"));
+    }
+}
@@ -7,6 +7,29 @@ pub enum CursorError {
 
     /// Returned when the base64 encoding on the cursor is invalid.
     InvalidCursorEncoding,
+
+    /// Returned when cursor signing is enabled and the cursor's signature is missing, was made with an
+    /// unknown key, or doesn't match its contents - ie the cursor has been tampered with.
+    InvalidCursorSignature,
+
+    /// Returned when cursor encryption is enabled and the cursor couldn't be decrypted - it was
+    /// encrypted with an unknown or different key, or it has been corrupted or tampered with.
+    CursorDecryptionFailed,
+
+    /// Returned when the cursor was built with a version of the cursor's format that's neither the
+    /// current one nor one there's an upgrade decoder for.
+    UnsupportedCursorVersion(u32),
+
+    /// Returned when the `first` or `last` of a page request is negative.
+    NegativePageSize,
+
+    /// Returned when the cursors for a page can't be built without the total count of the result set,
+    /// and there isn't one - such as offset cursors for `last` without `before`.
+    TotalCountRequired,
+
+    /// Returned when an edge whose cursor is non-null, such as one with `strict` nullability, is
+    /// built without a cursor.
+    MissingCursor,
 }
 
 impl std::fmt::Display for CursorError {
@@ -14,6 +37,19 @@ impl std::fmt::Display for CursorError {
         match self {
             CursorError::InvalidCursor => write!(f, "Invalid cursor"),
             CursorError::InvalidCursorEncoding => write!(f, "Invalid cursor encoding"),
+            CursorError::InvalidCursorSignature => write!(f, "Invalid cursor signature"),
+            CursorError::CursorDecryptionFailed => write!(f, "Cursor decryption failed"),
+            CursorError::UnsupportedCursorVersion(version) => {
+                write!(f, "Unsupported cursor version {version}")
+            }
+            CursorError::NegativePageSize => write!(f, "first and last must not be negative"),
+            CursorError::TotalCountRequired => {
+                write!(
+                    f,
+                    "The total count is required to build cursors for this page"
+                )
+            }
+            CursorError::MissingCursor => write!(f, "The edge needs a cursor"),
         }
     }
 }
@@ -41,6 +77,30 @@ mod tests {
             format!("{}", CursorError::InvalidCursorEncoding),
             "Invalid cursor encoding"
         );
+        assert_eq!(
+            format!("{}", CursorError::InvalidCursorSignature),
+            "Invalid cursor signature"
+        );
+        assert_eq!(
+            format!("{}", CursorError::CursorDecryptionFailed),
+            "Cursor decryption failed"
+        );
+        assert_eq!(
+            format!("{}", CursorError::UnsupportedCursorVersion(3)),
+            "Unsupported cursor version 3"
+        );
+        assert_eq!(
+            format!("{}", CursorError::NegativePageSize),
+            "first and last must not be negative"
+        );
+        assert_eq!(
+            format!("{}", CursorError::TotalCountRequired),
+            "The total count is required to build cursors for this page"
+        );
+        assert_eq!(
+            format!("{}", CursorError::MissingCursor),
+            "The edge needs a cursor"
+        );
     }
 
     #[test]
@@ -1,13 +1,32 @@
-use juniper_relay_helpers::{Cursor, OffsetCursor, PageInfo, PageRequest};
+use juniper_relay_helpers::{Cursor, CursorError, OffsetCursor, PageInfo, PageRequest};
 
 /// Struct that holds metadata about the response that can be used in the CursorProvider
 #[derive(Debug, Clone)]
 pub struct PaginationMetadata {
-    /// The total number of items in the result set:
-    pub total_count: i32,
+    /// The total number of items in the result set, if it's known:
+    pub total_count: Option<i32>,
 
     /// The current PageInfo, if any:
     pub page_request: Option<PageRequest>,
+
+    /// Whether there are more items past the page in the direction of pagination - following it for
+    /// `first`, preceding it for `last`. Set when the page was fetched with one extra item to find out,
+    /// see `NullableCountConnection::new_overfetched`.
+    pub has_more: Option<bool>,
+}
+
+impl PaginationMetadata {
+    /// The `has_more` hint, if the page request was paginating in the given direction.
+    fn has_more_in_direction(&self, forward: bool) -> Option<bool> {
+        let pr = self.page_request.as_ref()?;
+        let paginating_forward = pr.first.is_some();
+        let paginating_backward = !paginating_forward && pr.last.is_some();
+        match forward {
+            true if paginating_forward => self.has_more,
+            false if paginating_backward => self.has_more,
+            _ => None,
+        }
+    }
 }
 
 /// Trait to implement when building a Relay cursor provider.
@@ -15,104 +34,223 @@ pub struct PaginationMetadata {
 /// Cursor providers are how we generate cursors for each of the individual items
 /// within the result set, without needing to do a pass and build them manually.
 ///
-pub trait CursorProvider {
+/// The provider is generic over `N`, the type of node it builds cursors for, so implementations
+/// can read whatever they need off the item itself. Positional providers like
+/// `OffsetCursorProvider` implement it for every `N`.
+///
+pub trait CursorProvider<N> {
     /// Build a cursor instance for the given item, with helper metadata etc.
     ///
     /// `metadata` is information about the current resultset we're building for.
     /// `item_idx` is the index of the item we're building a cursor for.
     /// `item` is the item itself.
-    fn get_cursor_for_item<T>(
+    fn get_cursor_for_item(
         &self,
         metadata: &PaginationMetadata,
         item_idx: i32,
-        item: &T,
+        item: &N,
     ) -> impl Cursor;
 
     /// Builds the `PageInfo` to return to the RelayConnection
-    fn get_page_info<T>(&self, metadata: &PaginationMetadata, items: &[T]) -> PageInfo;
+    fn get_page_info(&self, metadata: &PaginationMetadata, items: &[N]) -> PageInfo;
+
+    /// Whether the cursors for a page depend on the total count of the result set, such as when
+    /// they're positions counted back from the end of it. `RelayConnection::new_for_selection` uses
+    /// this to fetch the total for the cursors even when `count` isn't selected.
+    fn requires_total_count(&self, _page_request: Option<&PageRequest>) -> bool {
+        false
+    }
+}
+
+/// Allows a provider to be passed by reference, so the same instance can be used for more than one
+/// connection.
+impl<N, P> CursorProvider<N> for &P
+where
+    P: CursorProvider<N>,
+{
+    fn get_cursor_for_item(
+        &self,
+        metadata: &PaginationMetadata,
+        item_idx: i32,
+        item: &N,
+    ) -> impl Cursor {
+        (*self).get_cursor_for_item(metadata, item_idx, item)
+    }
+
+    fn get_page_info(&self, metadata: &PaginationMetadata, items: &[N]) -> PageInfo {
+        (*self).get_page_info(metadata, items)
+    }
+
+    fn requires_total_count(&self, page_request: Option<&PageRequest>) -> bool {
+        (*self).requires_total_count(page_request)
+    }
+}
+
+/// Builds the `PageInfo` for providers whose cursors don't say where the page sits within the whole
+/// result set, using only the page request and the items that came back.
+///
+/// Unless the page was overfetched to find out, a full page is assumed to have more items following it
+/// (or preceding it, when paginating backwards), so the final page of a set that divides exactly into
+/// pages will still report that there's a next page.
+pub(crate) fn page_info_from_request<N, C: Cursor>(
+    metadata: &PaginationMetadata,
+    items: &[N],
+    cursor_for_item: impl Fn(i32, &N) -> C,
+) -> PageInfo {
+    let items_len = items.len() as i32;
+    let has_more = |limit: Option<i32>, forward: bool| {
+        metadata.has_more_in_direction(forward).unwrap_or_else(|| {
+            limit.is_some_and(|limit| items_len >= limit)
+                && metadata.total_count.is_none_or(|total| items_len < total)
+        })
+    };
+
+    let (has_prev_page, has_next_page) = match &metadata.page_request {
+        Some(pr) => (
+            pr.after.is_some() || has_more(pr.last, false),
+            pr.before.is_some() || has_more(pr.first, true),
+        ),
+        None => (false, false),
+    };
+
+    PageInfo {
+        has_prev_page,
+        has_next_page,
+        start_cursor: items
+            .first()
+            .map(|item| cursor_for_item(0, item).to_encoded_string()),
+        end_cursor: items
+            .last()
+            .map(|item| cursor_for_item(items_len - 1, item).to_encoded_string()),
+    }
 }
 
 // -------------- OffsetCursorProvider ---------------
 
 /// Built-in cursor provider that can handle Offset cursors. Serves as a reference implementation for
 /// your own cursor providers too.
+///
+/// Handles both forward (`first` / `after`) and backward (`last` / `before`) pagination. The position
+/// of the page within the full result set is worked out following the Relay spec: `after` and
+/// `before` narrow the set down, then `first` takes from the start of it and `last` from the end.
+///
+/// Without a total count, `last` can only be placed within the result set when there's a `before`
+/// cursor to count back from. `requires_total_count` says when that's the case, and the provider
+/// panics if it's then given metadata without a total. The `RelayConnection` builders always fetch
+/// the total when it's required, or return `CursorError::TotalCountRequired` if they can't.
 pub struct OffsetCursorProvider;
-impl CursorProvider for OffsetCursorProvider {
-    fn get_cursor_for_item<T>(
+impl<N> CursorProvider<N> for OffsetCursorProvider {
+    fn get_cursor_for_item(
         &self,
         metadata: &PaginationMetadata,
         item_idx: i32,
-        _item: &T,
+        _item: &N,
     ) -> impl Cursor {
-        // OK this is annoying. If there _was_ a cursor passed to `after`, the offset needs to start
-        // at the next item. If there wasn't, the offset needs to start at the first item (0).
-        let mut offset_adjust = 0;
-
-        let default_cursor = OffsetCursor::default();
-        let current_cursor = match &metadata.page_request {
-            Some(pr) => match pr.parsed_cursor() {
-                Ok(c) => match c {
-                    Some(cc) => {
-                        offset_adjust = 1;
-                        cc
-                    }
-                    None => default_cursor,
-                },
-                Err(_) => default_cursor,
-            },
-            None => default_cursor,
-        };
-
+        let window = OffsetWindow::for_provider(metadata);
         OffsetCursor {
-            offset: current_cursor.offset + offset_adjust + item_idx,
-            first: current_cursor.first,
+            offset: window.start.saturating_add(item_idx),
+            first: window.first,
         }
     }
 
-    fn get_page_info<T>(&self, metadata: &PaginationMetadata, items: &[T]) -> PageInfo {
-        let default_cursor = OffsetCursor::default();
-        let current_cursor = match &metadata.page_request {
-            Some(pr) => match pr.parsed_cursor() {
-                Ok(c) => c.unwrap_or(default_cursor),
-                Err(_) => default_cursor,
+    fn get_page_info(&self, metadata: &PaginationMetadata, items: &[N]) -> PageInfo {
+        let window = OffsetWindow::for_provider(metadata);
+
+        PageInfo {
+            has_prev_page: window.start > 0,
+            has_next_page: match metadata.total_count {
+                Some(total_count) => window.end.is_some_and(|end| end < total_count),
+                None => {
+                    metadata.has_more_in_direction(true).unwrap_or(false)
+                        || metadata
+                            .page_request
+                            .as_ref()
+                            .is_some_and(|pr| pr.before.is_some())
+                }
             },
-            None => default_cursor,
-        };
+            start_cursor: items.first().map(|item| {
+                self.get_cursor_for_item(metadata, 0, item)
+                    .to_encoded_string()
+            }),
+            end_cursor: items.last().map(|item| {
+                self.get_cursor_for_item(metadata, items.len() as i32 - 1, item)
+                    .to_encoded_string()
+            }),
+        }
+    }
+
+    fn requires_total_count(&self, page_request: Option<&PageRequest>) -> bool {
+        // `last` counts back from the end of the result set, unless `first` or `before` bound it.
+        page_request.is_some_and(|pr| {
+            pr.last.is_some()
+                && pr.first.is_none()
+                && !matches!(pr.parsed_before_cursor::<OffsetCursor>(), Ok(Some(_)))
+        })
+    }
+}
+
+/// The slice of the full result set that a `PageRequest` points at, in offsets.
+struct OffsetWindow {
+    /// Offset of the first item in the page.
+    start: i32,
 
-        let has_next_page = if let Some(pr) = &metadata.page_request {
-            // Check if we requested up to or over the total items.
-            if let Some(first) = pr.first {
-                current_cursor.offset + first < metadata.total_count
-            } else {
-                false
-            }
-        } else {
-            // We didn't request a first, which means the entire result set, therefore no next page
-            false
+    /// Offset one past the last item in the page. Without `first`, `before` or a total count, the end
+    /// of the result set isn't known so the window is left open.
+    end: Option<i32>,
+
+    /// The `first` carried over from the cursor the request was made with.
+    first: Option<i32>,
+}
+
+impl OffsetWindow {
+    /// Works out the window, failing with `CursorError::TotalCountRequired` when it's counted back
+    /// from the end of a result set whose total count isn't known.
+    fn from_metadata(metadata: &PaginationMetadata) -> Result<Self, CursorError> {
+        let total_count = metadata.total_count;
+        let Some(pr) = &metadata.page_request else {
+            // No request means the entire result set.
+            return Ok(OffsetWindow {
+                start: 0,
+                end: total_count,
+                first: None,
+            });
         };
 
-        let last_index = items.len() - 1;
+        let after = pr.parsed_cursor::<OffsetCursor>().ok().flatten();
+        let before = pr.parsed_before_cursor::<OffsetCursor>().ok().flatten();
 
-        PageInfo {
-            has_prev_page: current_cursor.offset > 0,
-            has_next_page,
-            start_cursor: if !items.is_empty() {
-                Some(
-                    self.get_cursor_for_item(metadata, 0, &items[0])
-                        .to_encoded_string(),
-                )
-            } else {
-                None
-            },
-            end_cursor: if !items.is_empty() {
-                Some(
-                    self.get_cursor_for_item(metadata, last_index as i32, &items[last_index])
-                        .to_encoded_string(),
-                )
-            } else {
-                None
-            },
+        // Remember that `after` means after - the page starts at the _next_ item along. `before`
+        // on the other hand is already one past the end of the page.
+        let mut start = after.as_ref().map_or(0, |c| c.offset.saturating_add(1));
+        let mut end = match (&before, total_count) {
+            (Some(before), Some(total_count)) => Some(before.offset.min(total_count)),
+            (Some(before), None) => Some(before.offset),
+            (None, total_count) => total_count,
+        }
+        .map(|end| end.max(start));
+
+        if let Some(first) = pr.first {
+            let first_end = start.saturating_add(first.max(0));
+            end = Some(end.map_or(first_end, |end| end.min(first_end)));
+        }
+        if let Some(last) = pr.last {
+            // `last` counts back from the end, so the end has to be known.
+            let end = end.ok_or(CursorError::TotalCountRequired)?;
+            start = start.max(end.saturating_sub(last.max(0)));
         }
+
+        Ok(OffsetWindow {
+            start,
+            end,
+            first: after.or(before).and_then(|c| c.first),
+        })
+    }
+
+    /// Works out the window for the provider, which can't return an error.
+    fn for_provider(metadata: &PaginationMetadata) -> Self {
+        Self::from_metadata(metadata).expect(
+            "OffsetCursorProvider needs the total count for this page, see `requires_total_count`",
+        )
     }
 }
 
@@ -130,11 +268,59 @@ impl OffsetCursorProvider {
     }
 }
 
+// -------------- NodeCursorProvider ---------------
+
+/// Cursor provider that builds each cursor from the node itself, using the function you give it.
+/// Handy for cursors built from IDs, timestamps or tokens that are already on the node:
+///
+/// ```
+/// use juniper_relay_helpers::{NodeCursorProvider, StringCursor};
+///
+/// struct Character {
+///     id: String,
+///     name: String,
+/// }
+///
+/// let provider = NodeCursorProvider::new(|character: &Character| StringCursor::new(character.id.clone()));
+/// ```
+///
+/// The `PageInfo` is worked out from the page request, the same way as `KeysetCursorProvider`.
+pub struct NodeCursorProvider<F> {
+    cursor_fn: F,
+}
+
+impl<F> NodeCursorProvider<F> {
+    /// Creates the provider from the function that builds a cursor for a node.
+    pub fn new(cursor_fn: F) -> Self {
+        NodeCursorProvider { cursor_fn }
+    }
+}
+
+impl<N, C, F> CursorProvider<N> for NodeCursorProvider<F>
+where
+    C: Cursor,
+    F: Fn(&N) -> C,
+{
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata,
+        _item_idx: i32,
+        item: &N,
+    ) -> impl Cursor {
+        (self.cursor_fn)(item)
+    }
+
+    fn get_page_info(&self, metadata: &PaginationMetadata, items: &[N]) -> PageInfo {
+        page_info_from_request(metadata, items, |_, item| (self.cursor_fn)(item))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     mod offset_cursor_provider {
+        use crate::cursor_provider::OffsetWindow;
         use crate::{
-            Cursor, CursorProvider, OffsetCursor, OffsetCursorProvider, PageRequest,
+            Cursor, CursorError, CursorProvider, OffsetCursor, OffsetCursorProvider, PageRequest,
             PaginationMetadata,
         };
 
@@ -162,7 +348,8 @@ mod tests {
             let p = OffsetCursorProvider::new();
             let pi = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: 2,
+                    total_count: Some(2),
+                    has_more: None,
                     page_request: None,
                 },
                 &data(),
@@ -200,7 +387,8 @@ mod tests {
             let p = OffsetCursorProvider::new();
             let pi = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: 27,
+                    total_count: Some(27),
+                    has_more: None,
                     page_request: None,
                 },
                 &data(),
@@ -236,10 +424,13 @@ mod tests {
             let p = OffsetCursorProvider::new();
             let pi = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: 27,
+                    total_count: Some(27),
+                    has_more: None,
                     page_request: Some(PageRequest {
                         first: Some(10),
                         after: None,
+                        last: None,
+                        before: None,
                     }),
                 },
                 &data(),
@@ -294,10 +485,13 @@ mod tests {
 
             let pi1 = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: total_items,
+                    total_count: Some(total_items),
+                    has_more: None,
                     page_request: Some(PageRequest {
                         first: Some(5),
                         after: None,
+                        last: None,
+                        before: None,
                     }),
                 },
                 &data,
@@ -327,10 +521,13 @@ mod tests {
 
             let pi2 = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: total_items,
+                    total_count: Some(total_items),
+                    has_more: None,
                     page_request: Some(PageRequest {
                         first: Some(5),
                         after: pi1.end_cursor.clone(),
+                        last: None,
+                        before: None,
                     }),
                 },
                 &data,
@@ -360,10 +557,13 @@ mod tests {
 
             let pi3 = p.get_page_info(
                 &PaginationMetadata {
-                    total_count: total_items,
+                    total_count: Some(total_items),
+                    has_more: None,
                     page_request: Some(PageRequest {
                         first: Some(5),
                         after: pi2.end_cursor.clone(),
+                        last: None,
+                        before: None,
                     }),
                 },
                 &[data[0].clone(), data[1].clone(), data[2].clone()],
@@ -391,5 +591,377 @@ mod tests {
                 )
             );
         }
+
+        /// Mimics a first page request with an empty result set.
+        #[test]
+        fn test_page_info_empty_results() {
+            let p = OffsetCursorProvider::new();
+            let pi = CursorProvider::<Location>::get_page_info(
+                &p,
+                &PaginationMetadata {
+                    total_count: Some(0),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: Some(10),
+                        after: None,
+                        last: None,
+                        before: None,
+                    }),
+                },
+                &[],
+            );
+
+            assert!(!pi.has_prev_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(pi.start_cursor, None);
+            assert_eq!(pi.end_cursor, None);
+        }
+
+        /// Mimics asking for the last items of the set with no `before` cursor.
+        #[test]
+        fn test_page_info_last_page_no_before() {
+            let p = OffsetCursorProvider::new();
+            let pi = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(13),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: None,
+                        after: None,
+                        last: Some(2),
+                        before: None,
+                    }),
+                },
+                &data(),
+            );
+
+            assert!(pi.has_prev_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(11, None).to_encoded_string())
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(OffsetCursor::new(12, None).to_encoded_string())
+            );
+        }
+
+        /// Test mimics paginating backwards from the end of the set until reaching the start.
+        #[test]
+        fn test_page_info_paginating_backwards_through_set() {
+            let p = OffsetCursorProvider::new();
+            let total_items = 5;
+
+            let pi1 = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(total_items),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: None,
+                        after: None,
+                        last: Some(2),
+                        before: None,
+                    }),
+                },
+                &data(),
+            );
+            assert!(pi1.has_prev_page);
+            assert!(!pi1.has_next_page);
+            assert_eq!(
+                pi1.start_cursor,
+                Some(OffsetCursor::new(3, None).to_encoded_string())
+            );
+            assert_eq!(
+                pi1.end_cursor,
+                Some(OffsetCursor::new(4, None).to_encoded_string())
+            );
+
+            let pi2 = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(total_items),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: None,
+                        after: None,
+                        last: Some(2),
+                        before: pi1.start_cursor.clone(),
+                    }),
+                },
+                &data(),
+            );
+            assert!(pi2.has_prev_page);
+            assert!(pi2.has_next_page);
+            assert_eq!(
+                pi2.start_cursor,
+                Some(OffsetCursor::new(1, None).to_encoded_string())
+            );
+            assert_eq!(
+                pi2.end_cursor,
+                Some(OffsetCursor::new(2, None).to_encoded_string())
+            );
+
+            // Only one item left before the cursor, even though we asked for two:
+            let pi3 = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(total_items),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: None,
+                        after: None,
+                        last: Some(2),
+                        before: pi2.start_cursor.clone(),
+                    }),
+                },
+                &data()[..1],
+            );
+            assert!(!pi3.has_prev_page);
+            assert!(pi3.has_next_page);
+            assert_eq!(
+                pi3.start_cursor,
+                Some(OffsetCursor::new(0, None).to_encoded_string())
+            );
+            assert_eq!(
+                pi3.end_cursor,
+                Some(OffsetCursor::new(0, None).to_encoded_string())
+            );
+        }
+
+        /// Mimics a request that uses both `after` and `before` to ask for a range of the set.
+        #[test]
+        fn test_page_info_after_and_before() {
+            let p = OffsetCursorProvider::new();
+            let pi = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(13),
+                    has_more: None,
+                    page_request: Some(PageRequest {
+                        first: None,
+                        after: Some(OffsetCursor::new(3, Some(2)).to_encoded_string()),
+                        last: None,
+                        before: Some(OffsetCursor::new(6, Some(2)).to_encoded_string()),
+                    }),
+                },
+                &data(),
+            );
+
+            assert!(pi.has_prev_page);
+            assert!(pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(4, Some(2)).to_encoded_string())
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(OffsetCursor::new(5, Some(2)).to_encoded_string())
+            );
+        }
+
+        /// Without a total count, the next page comes from whether the page was overfetched.
+        #[test]
+        fn test_page_info_without_total_count() {
+            let p = OffsetCursorProvider::new();
+            let metadata = |has_more| PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(
+                    Some(2),
+                    Some(OffsetCursor::new(3, Some(2))),
+                )),
+                has_more: Some(has_more),
+            };
+
+            let pi = p.get_page_info(&metadata(true), &data());
+            assert!(pi.has_prev_page);
+            assert!(pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(4, Some(2)).to_encoded_string())
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(OffsetCursor::new(5, Some(2)).to_encoded_string())
+            );
+
+            let pi = p.get_page_info(&metadata(false), &data());
+            assert!(pi.has_prev_page);
+            assert!(!pi.has_next_page);
+        }
+
+        /// `last` without `before` counts back from the end of the result set, so it can't be placed
+        /// without a total count.
+        #[test]
+        fn test_last_without_total_count() {
+            let p = OffsetCursorProvider::new();
+            let metadata = |page_request: PageRequest| PaginationMetadata {
+                total_count: None,
+                page_request: Some(page_request),
+                has_more: Some(true),
+            };
+
+            let from_end = PageRequest::new_backward(Some(2), None::<OffsetCursor>);
+            assert!(CursorProvider::<Location>::requires_total_count(
+                &p,
+                Some(&from_end)
+            ));
+            assert_eq!(
+                OffsetWindow::from_metadata(&metadata(from_end)).err(),
+                Some(CursorError::TotalCountRequired)
+            );
+
+            let before = PageRequest::new_backward(Some(2), Some(OffsetCursor::new(6, None)));
+            assert!(!CursorProvider::<Location>::requires_total_count(
+                &p,
+                Some(&before)
+            ));
+            let pi = p.get_page_info(&metadata(before), &data());
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(4, None).to_encoded_string())
+            );
+            assert!(pi.has_prev_page);
+            assert!(pi.has_next_page);
+        }
+
+        /// Cursors come from clients, so offsets at the very ends of the range mustn't overflow.
+        #[test]
+        fn test_page_info_extreme_cursor_offsets() {
+            let p = OffsetCursorProvider::new();
+            let metadata = |page_request| PaginationMetadata {
+                total_count: Some(13),
+                has_more: None,
+                page_request: Some(page_request),
+            };
+
+            let pi = p.get_page_info(
+                &metadata(PageRequest::new(
+                    Some(2),
+                    Some(OffsetCursor::new(i32::MAX, None)),
+                )),
+                &data(),
+            );
+            assert!(pi.has_prev_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(
+                pi.end_cursor,
+                Some(OffsetCursor::new(i32::MAX, None).to_encoded_string())
+            );
+
+            let pi = p.get_page_info(
+                &metadata(PageRequest::new_backward(
+                    Some(2),
+                    Some(OffsetCursor::new(i32::MIN, None)),
+                )),
+                &data(),
+            );
+            assert!(!pi.has_prev_page);
+            assert!(pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(OffsetCursor::new(0, None).to_encoded_string())
+            );
+        }
+    }
+
+    mod node_cursor_provider {
+        use crate::{
+            Cursor, CursorProvider, NodeCursorProvider, OffsetCursorProvider, PageRequest,
+            PaginationMetadata, StringCursor,
+        };
+
+        #[derive(Debug, Clone)]
+        struct Character {
+            id: String,
+        }
+
+        fn data() -> Vec<Character> {
+            vec![
+                Character {
+                    id: "lune".to_owned(),
+                },
+                Character {
+                    id: "sciel".to_owned(),
+                },
+            ]
+        }
+
+        #[test]
+        fn test_cursor_built_from_item() {
+            let p = NodeCursorProvider::new(|c: &Character| StringCursor::new(c.id.clone()));
+            let data = data();
+            let cursor = p.get_cursor_for_item(
+                &PaginationMetadata {
+                    total_count: Some(2),
+                    has_more: None,
+                    page_request: None,
+                },
+                1,
+                &data[1],
+            );
+            assert_eq!(cursor.to_raw_string(), "string:sciel");
+        }
+
+        #[test]
+        fn test_page_info() {
+            let p = NodeCursorProvider::new(|c: &Character| StringCursor::new(c.id.clone()));
+            let pi = p.get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    has_more: None,
+                    page_request: Some(PageRequest::new(
+                        Some(2),
+                        Some(StringCursor::new("maelle".to_owned())),
+                    )),
+                },
+                &data(),
+            );
+
+            assert!(pi.has_prev_page);
+            assert!(pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(StringCursor::new("lune".to_owned()).to_encoded_string())
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(StringCursor::new("sciel".to_owned()).to_encoded_string())
+            );
+        }
+
+        /// `has_more` wins over guessing from whether the page is full.
+        #[test]
+        fn test_page_info_has_more() {
+            let p = NodeCursorProvider::new(|c: &Character| StringCursor::new(c.id.clone()));
+            let forward = |has_more| PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new(Some(2), None::<StringCursor>)),
+                has_more: Some(has_more),
+            };
+            assert!(p.get_page_info(&forward(true), &data()).has_next_page);
+            assert!(!p.get_page_info(&forward(false), &data()).has_next_page);
+            assert!(!p.get_page_info(&forward(true), &data()).has_prev_page);
+
+            let backward = |has_more| PaginationMetadata {
+                total_count: None,
+                page_request: Some(PageRequest::new_backward(Some(2), None::<StringCursor>)),
+                has_more: Some(has_more),
+            };
+            assert!(p.get_page_info(&backward(true), &data()).has_prev_page);
+            assert!(!p.get_page_info(&backward(false), &data()).has_prev_page);
+            assert!(!p.get_page_info(&backward(true), &data()).has_next_page);
+        }
+
+        #[test]
+        fn test_provider_by_reference() {
+            let p = OffsetCursorProvider::new();
+            let metadata = PaginationMetadata {
+                total_count: Some(2),
+                has_more: None,
+                page_request: None,
+            };
+            assert_eq!(
+                CursorProvider::<Character>::get_page_info(&&p, &metadata, &data()),
+                p.get_page_info(&metadata, &data())
+            );
+        }
     }
 }
@@ -0,0 +1,339 @@
+use crate::cursor_codec::global_cursor_codec;
+use crate::cursor_errors::CursorError;
+use crate::{Cursor, TextEncoding};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::OnceLock;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length in bytes of the HMAC-SHA256 signature appended to a signed cursor.
+const SIGNATURE_LEN: usize = 32;
+
+static CURSOR_SIGNER: OnceLock<CursorSigner> = OnceLock::new();
+
+/// A secret key used to sign cursors, along with an identifier so the right key can be found again
+/// when verifying. The identifier is stored in the cursor in the clear, so don't put anything
+/// sensitive in it.
+#[derive(Clone)]
+pub struct CursorSigningKey {
+    id: String,
+    secret: Vec<u8>,
+}
+
+impl CursorSigningKey {
+    /// Builds a key from its identifier and secret. Secrets should be at least 32 bytes of random data.
+    ///
+    /// Panics if the identifier is longer than 255 bytes.
+    pub fn new(id: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        let id = id.into();
+        assert!(
+            id.len() <= u8::MAX as usize,
+            "cursor key ids must be at most 255 bytes"
+        );
+        CursorSigningKey {
+            id,
+            secret: secret.into(),
+        }
+    }
+
+    /// The identifier of the key.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl std::fmt::Debug for CursorSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorSigningKey")
+            .field("id", &self.id)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Signs cursors with HMAC-SHA256 so that clients can't hand-craft or alter them.
+///
+/// New cursors are always signed with the signing key. Cursors are accepted if they were signed with
+/// the signing key or any of the verification keys, which allows keys to be rotated without
+/// invalidating the cursors clients are already holding:
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, CursorSigner, CursorSigningKey, OffsetCursor};
+///
+/// let signer = CursorSigner::new(CursorSigningKey::new("2025-05", "new-secret-from-your-config"))
+///     .with_verification_key(CursorSigningKey::new("2025-04", "old-secret-from-your-config"));
+///
+/// let encoded = signer.sign(&OffsetCursor::new(10, Some(10)));
+/// let decoded = signer.verify::<OffsetCursor>(&encoded).unwrap();
+/// ```
+///
+/// Most of the time you'll want to install the signer once at startup with `set_cursor_signer`, so
+/// that every cursor is signed and verified as part of `Cursor::to_encoded_string` and
+/// `Cursor::from_encoded_string`.
+#[derive(Debug, Clone)]
+pub struct CursorSigner {
+    signing_key: CursorSigningKey,
+    verification_keys: Vec<CursorSigningKey>,
+}
+
+impl CursorSigner {
+    /// Creates a signer that signs and verifies with the given key.
+    pub fn new(signing_key: CursorSigningKey) -> Self {
+        CursorSigner {
+            signing_key,
+            verification_keys: vec![],
+        }
+    }
+
+    /// Adds a key that cursors will still be accepted from, but that new cursors won't be signed with.
+    pub fn with_verification_key(mut self, key: CursorSigningKey) -> Self {
+        self.verification_keys.push(key);
+        self
+    }
+
+    /// Signs a raw cursor string, returning the string to hand to clients encoded with the text
+    /// encoding of the installed `CursorCodec`.
+    pub fn sign_raw(&self, raw: &str) -> String {
+        self.sign_encoded(raw, global_cursor_codec().text_encoding())
+    }
+
+    /// Verifies a string produced by `sign_raw` and returns the raw cursor string.
+    /// Returns `CursorError::InvalidCursorSignature` if the signature is wrong or the key is unknown.
+    pub fn verify_raw(&self, input: &str) -> Result<String, CursorError> {
+        self.verify_encoded(input, global_cursor_codec().text_encoding())
+    }
+
+    /// Signs the bytes of a cursor. The signed bytes are the length of the key id, the key id, the
+    /// cursor bytes and then the signature of everything before it.
+    pub fn sign_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut payload = key_id_header(&self.signing_key.id);
+        payload.extend_from_slice(bytes);
+
+        let mut mac = self.signing_key.mac();
+        mac.update(&payload);
+        payload.extend_from_slice(&mac.finalize().into_bytes());
+        payload
+    }
+
+    /// Verifies bytes produced by `sign_bytes`, returning the cursor bytes that were signed.
+    pub fn verify_bytes(&self, signed: &[u8]) -> Result<Vec<u8>, CursorError> {
+        if signed.len() < SIGNATURE_LEN {
+            return Err(CursorError::InvalidCursorSignature);
+        }
+        let (payload, signature) = signed.split_at(signed.len() - SIGNATURE_LEN);
+        let (id, bytes) =
+            split_key_id_header(payload).ok_or(CursorError::InvalidCursorSignature)?;
+
+        let key = std::iter::once(&self.signing_key)
+            .chain(self.verification_keys.iter())
+            .find(|key| key.id.as_bytes() == id)
+            .ok_or(CursorError::InvalidCursorSignature)?;
+
+        // `verify_slice` compares in constant time.
+        let mut mac = key.mac();
+        mac.update(payload);
+        mac.verify_slice(signature)
+            .map_err(|_| CursorError::InvalidCursorSignature)?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Signs a cursor, returning the string to hand to clients encoded with the text encoding of the
+    /// cursor's codec.
+    pub fn sign<C: Cursor + ?Sized>(&self, cursor: &C) -> String {
+        self.sign_encoded(
+            &cursor.to_versioned_raw_string(),
+            C::codec().text_encoding(),
+        )
+    }
+
+    /// Verifies a signed cursor string and decodes it into the cursor type.
+    pub fn verify<C: Cursor + ?Sized>(&self, input: &str) -> Result<C::CursorType, CursorError> {
+        C::from_raw_string(&self.verify_encoded(input, C::codec().text_encoding())?)
+    }
+
+    fn sign_encoded(&self, raw: &str, text_encoding: TextEncoding) -> String {
+        text_encoding.encode(&self.sign_bytes(raw.as_bytes()))
+    }
+
+    fn verify_encoded(
+        &self,
+        input: &str,
+        text_encoding: TextEncoding,
+    ) -> Result<String, CursorError> {
+        let decoded = text_encoding.decode(input)?;
+        Ok(String::from_utf8(self.verify_bytes(&decoded)?)?)
+    }
+}
+
+/// Installs a signer that every cursor will be signed and verified with from then on. Call this once
+/// at startup, before any cursors are built.
+///
+/// Only one signer can be installed; if one already is, the signer passed in is handed back.
+pub fn set_cursor_signer(signer: CursorSigner) -> Result<(), CursorSigner> {
+    CURSOR_SIGNER.set(signer)
+}
+
+/// The signer installed with `set_cursor_signer`, if any.
+pub(crate) fn global_cursor_signer() -> Option<&'static CursorSigner> {
+    CURSOR_SIGNER.get()
+}
+
+/// Builds the header that identifies which key a cursor was protected with: the length of the key
+/// id as a single byte, followed by the key id.
+pub(crate) fn key_id_header(id: &str) -> Vec<u8> {
+    let mut header = Vec::with_capacity(1 + id.len());
+    header.push(id.len() as u8);
+    header.extend_from_slice(id.as_bytes());
+    header
+}
+
+/// Splits bytes starting with a `key_id_header` into the key id and the remaining bytes.
+pub(crate) fn split_key_id_header(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (id_len, rest) = bytes.split_first()?;
+    if rest.len() < *id_len as usize {
+        return None;
+    }
+    Some(rest.split_at(*id_len as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Cursor, CursorCodec, CursorError, CursorSigner, CursorSigningKey, OffsetCursor,
+        RelayCursor, StringCursor, TextEncoding,
+    };
+    use base64::prelude::*;
+
+    static UNPADDED: CursorCodec =
+        CursorCodec::new().with_text_encoding(TextEncoding::UrlSafeNoPad);
+
+    #[derive(Debug, RelayCursor, Eq, PartialEq)]
+    #[cursor(tag = "page", codec = UNPADDED)]
+    struct PageCursor(u32);
+
+    fn signer() -> CursorSigner {
+        CursorSigner::new(CursorSigningKey::new("current", "current-secret"))
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = signer();
+        let encoded = signer.sign(&OffsetCursor::new(10, Some(5)));
+
+        let decoded = signer.verify::<OffsetCursor>(&encoded).unwrap();
+        assert_eq!(decoded.offset, 10);
+        assert_eq!(decoded.first, Some(5));
+    }
+
+    #[test]
+    fn test_signed_cursor_is_not_plain_cursor() {
+        let cursor = OffsetCursor::new(10, Some(5));
+        assert_ne!(signer().sign(&cursor), cursor.to_encoded_string());
+    }
+
+    #[test]
+    fn test_unsigned_cursor_rejected() {
+        let unsigned = OffsetCursor::new(10, Some(5)).to_encoded_string();
+        assert_eq!(
+            signer().verify::<OffsetCursor>(&unsigned).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_tampered_cursor_rejected() {
+        let signer = signer();
+        let encoded = signer.sign(&StringCursor::new("user-1".to_string()));
+
+        let mut bytes = BASE64_URL_SAFE.decode(&encoded).unwrap();
+        let user_idx = bytes.iter().position(|b| *b == b'1').unwrap();
+        bytes[user_idx] = b'2';
+        let tampered = BASE64_URL_SAFE.encode(bytes);
+
+        assert_eq!(
+            signer.verify::<StringCursor>(&tampered).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_wrong_key_rejected() {
+        let encoded = signer().sign(&OffsetCursor::new(10, Some(5)));
+
+        // Same key id, different secret:
+        let other = CursorSigner::new(CursorSigningKey::new("current", "another-secret"));
+        assert_eq!(
+            other.verify::<OffsetCursor>(&encoded).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+
+        // Unknown key id:
+        let other = CursorSigner::new(CursorSigningKey::new("other", "current-secret"));
+        assert_eq!(
+            other.verify::<OffsetCursor>(&encoded).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old_signer = CursorSigner::new(CursorSigningKey::new("old", "old-secret"));
+        let old_cursor = old_signer.sign(&OffsetCursor::new(3, None));
+
+        let rotated = signer().with_verification_key(CursorSigningKey::new("old", "old-secret"));
+        assert_eq!(
+            rotated.verify::<OffsetCursor>(&old_cursor).unwrap().offset,
+            3
+        );
+
+        // New cursors come from the signing key only:
+        let new_cursor = rotated.sign(&OffsetCursor::new(3, None));
+        assert_eq!(
+            signer().verify::<OffsetCursor>(&new_cursor).unwrap().offset,
+            3
+        );
+        assert_eq!(
+            old_signer.verify::<OffsetCursor>(&new_cursor).err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_truncated_input_rejected() {
+        assert_eq!(
+            signer().verify_raw("").err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+        assert_eq!(
+            signer()
+                .verify_raw(&BASE64_URL_SAFE.encode([7, b'c']))
+                .err(),
+            Some(CursorError::InvalidCursorSignature)
+        );
+    }
+
+    #[test]
+    fn test_sign_uses_cursor_text_encoding() {
+        let signer = signer();
+        let encoded = signer.sign(&PageCursor(12));
+        assert_eq!(
+            encoded,
+            TextEncoding::UrlSafeNoPad.encode(&signer.sign_bytes(b"page:12"))
+        );
+        assert!(!encoded.ends_with('='));
+        assert_eq!(signer.verify::<PageCursor>(&encoded), Ok(PageCursor(12)));
+    }
+
+    #[test]
+    fn test_key_debug_redacts_secret() {
+        let key = CursorSigningKey::new("current", "current-secret");
+        assert_eq!(key.id(), "current");
+        assert!(!format!("{:?}", key).contains("current-secret"));
+    }
+}
@@ -0,0 +1,295 @@
+use crate::cursor_errors::CursorError;
+
+/// Marks the start of the version prefix on the raw string of a versioned cursor.
+const VERSION_MARKER: char = '@';
+
+/// Function that decodes the raw string of an older version of a cursor and upgrades it into the
+/// current cursor type. Takes the same arguments as `Cursor::new`.
+pub type CursorUpgrader<T> = fn(raw: &str, parts: Vec<&str>) -> Result<T, CursorError>;
+
+/// The decoders for previous versions of a cursor's format, returned from `Cursor::upgraders`.
+///
+/// When a cursor is decoded with a different version to the current `Cursor::VERSION`, the decoder
+/// registered for that version is used to upgrade it. This means cursors issued by previous
+/// deployments keep working after the format changes.
+///
+/// ```
+/// use juniper_relay_helpers::{Cursor, CursorError, CursorUpgraders};
+///
+/// struct MessageCursor {
+///     channel: String,
+///     id: i32,
+/// }
+///
+/// impl Cursor for MessageCursor {
+///     type CursorType = MessageCursor;
+///
+///     // Version 1 didn't have the channel:
+///     const VERSION: u32 = 2;
+///
+///     fn to_raw_string(&self) -> String {
+///         format!("message:{}:{}", self.channel, self.id)
+///     }
+///
+///     fn new(_raw: &str, parts: Vec<&str>) -> Result<Self, CursorError> {
+///         let [_, channel, id] = parts[..] else { return Err(CursorError::InvalidCursor) };
+///         Ok(MessageCursor {
+///             channel: channel.to_string(),
+///             id: id.parse().map_err(|_| CursorError::InvalidCursor)?,
+///         })
+///     }
+///
+///     fn upgraders() -> CursorUpgraders<Self> {
+///         CursorUpgraders::new().register(1, |_raw, parts| {
+///             let [_, id] = parts[..] else { return Err(CursorError::InvalidCursor) };
+///             Ok(MessageCursor {
+///                 channel: "general".to_string(),
+///                 id: id.parse().map_err(|_| CursorError::InvalidCursor)?,
+///             })
+///         })
+///     }
+/// }
+/// ```
+pub struct CursorUpgraders<T> {
+    upgraders: Vec<(u32, CursorUpgrader<T>)>,
+}
+
+impl<T> CursorUpgraders<T> {
+    /// Creates an empty set of upgraders.
+    pub fn new() -> Self {
+        CursorUpgraders { upgraders: vec![] }
+    }
+
+    /// Registers the decoder for cursors of the given version.
+    pub fn register(mut self, version: u32, upgrader: CursorUpgrader<T>) -> Self {
+        self.upgraders.push((version, upgrader));
+        self
+    }
+
+    /// Decodes the raw string (without the version prefix) of a cursor of the given version using its
+    /// registered decoder. Returns `CursorError::UnsupportedCursorVersion` if there isn't one.
+    pub fn upgrade(&self, version: u32, raw: &str) -> Result<T, CursorError> {
+        let (_, upgrader) = self
+            .upgraders
+            .iter()
+            .find(|(v, _)| *v == version)
+            .ok_or(CursorError::UnsupportedCursorVersion(version))?;
+        upgrader(raw, raw.split(':').collect())
+    }
+}
+
+impl<T> Default for CursorUpgraders<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prefixes the raw string of a cursor with its version, ie `@2:message:general:12`.
+///
+/// Version 1 cursors aren't prefixed, so cursors issued before versioning existed are read as
+/// version 1.
+pub(crate) fn versioned_raw_string(version: u32, raw: &str) -> String {
+    if version == 1 {
+        raw.to_string()
+    } else {
+        format!("{VERSION_MARKER}{version}:{raw}")
+    }
+}
+
+/// Splits the version prefix off the raw string of a cursor, returning the version and the rest of
+/// the raw string. Returns `None` for raw strings without a version prefix, which are version 1.
+pub(crate) fn split_raw_string_version(raw: &str) -> Option<(u32, &str)> {
+    let (version, rest) = raw.strip_prefix(VERSION_MARKER)?.split_once(':')?;
+    Some((version.parse::<u32>().ok()?, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cursor_versions::{split_raw_string_version, versioned_raw_string};
+    use crate::{
+        Cursor, CursorError, CursorUpgraders, OffsetCursor, PageRequest, RelayCursor,
+        cursor_from_encoded_string,
+    };
+
+    /// The shape the cursor used to have, before `channel` was added.
+    #[derive(Debug, RelayCursor, Eq, PartialEq)]
+    #[cursor(tag = "message")]
+    struct MessageCursorV1 {
+        id: i32,
+    }
+
+    #[derive(Debug, RelayCursor, Eq, PartialEq)]
+    #[cursor(
+        tag = "message",
+        version = 2,
+        upgrade(version = 1, with = MessageCursor::from_v1)
+    )]
+    struct MessageCursor {
+        channel: String,
+        id: i32,
+    }
+
+    /// A hand-written cursor from before versioning, whose raw strings start with the version marker.
+    #[derive(Debug, Eq, PartialEq)]
+    struct MentionCursor {
+        user: String,
+        id: i32,
+    }
+
+    impl Cursor for MentionCursor {
+        type CursorType = MentionCursor;
+
+        fn to_raw_string(&self) -> String {
+            format!("@{}:{}", self.user, self.id)
+        }
+
+        fn new(_raw: &str, parts: Vec<&str>) -> Result<Self, CursorError> {
+            let [user, id] = parts[..] else {
+                return Err(CursorError::InvalidCursor);
+            };
+            Ok(MentionCursor {
+                user: user
+                    .strip_prefix('@')
+                    .ok_or(CursorError::InvalidCursor)?
+                    .to_string(),
+                id: id.parse().map_err(|_| CursorError::InvalidCursor)?,
+            })
+        }
+    }
+
+    impl MessageCursor {
+        fn from_v1(raw: &str, parts: Vec<&str>) -> Result<Self, CursorError> {
+            let v1 = <MessageCursorV1 as Cursor>::new(raw, parts)?;
+            Ok(MessageCursor {
+                channel: "general".to_string(),
+                id: v1.id,
+            })
+        }
+    }
+
+    #[test]
+    fn test_version_prefix() {
+        assert_eq!(versioned_raw_string(1, "offset:1"), "offset:1");
+        assert_eq!(versioned_raw_string(3, "offset:1"), "@3:offset:1");
+        assert_eq!(split_raw_string_version("offset:1"), None);
+        assert_eq!(
+            split_raw_string_version("@3:offset:1"),
+            Some((3, "offset:1"))
+        );
+        assert_eq!(split_raw_string_version("@x:offset:1"), None);
+        assert_eq!(split_raw_string_version("@3"), None);
+    }
+
+    #[test]
+    fn test_unversioned_raw_string_starting_with_marker() {
+        // The version prefix doesn't parse:
+        let cursor = MentionCursor {
+            user: "lune".to_string(),
+            id: 12,
+        };
+        assert_eq!(cursor.to_versioned_raw_string(), "@lune:12");
+        assert_eq!(
+            MentionCursor::from_encoded_string(&cursor.to_encoded_string()),
+            Ok(cursor)
+        );
+
+        // The version prefix parses, but isn't a version the cursor knows about:
+        let cursor = MentionCursor {
+            user: "2".to_string(),
+            id: 12,
+        };
+        assert_eq!(cursor.to_versioned_raw_string(), "@2:12");
+        assert_eq!(
+            MentionCursor::from_encoded_string(&cursor.to_encoded_string()),
+            Ok(cursor)
+        );
+
+        // Neither decodes, so the error from the versioned decode is kept:
+        assert_eq!(
+            MentionCursor::from_raw_string("@3:x:y"),
+            Err(CursorError::UnsupportedCursorVersion(3))
+        );
+        assert_eq!(
+            MentionCursor::from_raw_string("@x"),
+            Err(CursorError::InvalidCursor)
+        );
+    }
+
+    #[test]
+    fn test_unversioned_cursors_unchanged() {
+        assert_eq!(
+            OffsetCursor::new(1, Some(10)).to_encoded_string(),
+            "b2Zmc2V0OjE6MTA="
+        );
+    }
+
+    #[test]
+    fn test_current_version_round_trip() {
+        let cursor = MessageCursor {
+            channel: "general".to_string(),
+            id: 12,
+        };
+        assert_eq!(cursor.to_versioned_raw_string(), "@2:message:general:12");
+
+        let decoded = MessageCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_old_version_upgraded() {
+        let old = MessageCursorV1 { id: 12 }.to_encoded_string();
+
+        let decoded = cursor_from_encoded_string::<MessageCursor>(&old).unwrap();
+        assert_eq!(
+            decoded,
+            MessageCursor {
+                channel: "general".to_string(),
+                id: 12,
+            }
+        );
+
+        let request = PageRequest {
+            first: Some(10),
+            after: Some(old),
+            last: None,
+            before: None,
+        };
+        assert_eq!(
+            request
+                .parsed_cursor::<MessageCursor>()
+                .unwrap()
+                .unwrap()
+                .id,
+            12
+        );
+    }
+
+    #[test]
+    fn test_unknown_version_rejected() {
+        let newer = MessageCursor {
+            channel: "general".to_string(),
+            id: 12,
+        }
+        .to_encoded_string();
+
+        // The v1 cursor has no idea about version 2:
+        assert_eq!(
+            MessageCursorV1::from_encoded_string(&newer),
+            Err(CursorError::UnsupportedCursorVersion(2))
+        );
+    }
+
+    #[test]
+    fn test_upgraders() {
+        let upgraders = CursorUpgraders::<i32>::new()
+            .register(1, |_, parts| Ok(parts.len() as i32))
+            .register(2, |_, _| Err(CursorError::InvalidCursor));
+
+        assert_eq!(upgraders.upgrade(1, "a:b:c"), Ok(3));
+        assert_eq!(upgraders.upgrade(2, "a"), Err(CursorError::InvalidCursor));
+        assert_eq!(
+            upgraders.upgrade(3, "a"),
+            Err(CursorError::UnsupportedCursorVersion(3))
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use crate::cursor_codec::{CursorCodec, global_cursor_codec};
 use crate::cursor_errors::CursorError;
-use base64::prelude::*;
+use crate::cursor_versions::{CursorUpgraders, split_raw_string_version, versioned_raw_string};
 use juniper::{GraphQLScalar, ParseScalarResult, ParseScalarValue, ScalarToken, ScalarValue};
 use std::fmt::{Display, Formatter};
 
@@ -30,6 +31,13 @@ pub trait Cursor {
     /// Concrete type of the returned cursor. Usually the thing that implements the trait.
     type CursorType;
 
+    /// Version of the cursor's raw string format. Bump it whenever `to_raw_string` changes shape and
+    /// register a decoder for the previous version in `upgraders`, so that cursors clients are
+    /// already holding keep working.
+    ///
+    /// Cursors of any version other than 1 have their raw string prefixed with `@<version>:`.
+    const VERSION: u32 = 1;
+
     /// Serialize the cursor into a string ready to be base64 encoded.
     fn to_raw_string(&self) -> String;
 
@@ -37,18 +45,60 @@ pub trait Cursor {
     /// will return a Result of the CursorType. Return a CursorError if the decoding fails.
     fn new(raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError>;
 
-    /// Builds the CursorType from a base64 encoded string.
+    /// Decoders for the previous versions of the cursor's format, see `CursorUpgraders`.
+    /// By default there are none, so only cursors of the current `VERSION` are accepted.
+    fn upgraders() -> CursorUpgraders<Self::CursorType> {
+        CursorUpgraders::new()
+    }
+
+    /// The raw string from `to_raw_string`, prefixed with the cursor's `VERSION` when it isn't 1.
+    /// This is what gets encoded.
+    fn to_versioned_raw_string(&self) -> String {
+        versioned_raw_string(Self::VERSION, &self.to_raw_string())
+    }
+
+    /// Builds the CursorType from the raw string produced by `to_versioned_raw_string`, splitting it
+    /// into its colon separated parts and passing them to `new`.
+    ///
+    /// Raw strings of a previous version are handed to the matching decoder from `upgraders`. Raw
+    /// strings that start with `@` but can't be decoded as a versioned cursor are decoded as version 1,
+    /// as cursors issued before versioning existed could start with `@` too.
+    fn from_raw_string(raw: &str) -> Result<Self::CursorType, CursorError> {
+        let decode = |version: u32, raw: &str| {
+            if version == Self::VERSION {
+                Self::new(raw, raw.split(':').collect())
+            } else {
+                Self::upgraders().upgrade(version, raw)
+            }
+        };
+        match split_raw_string_version(raw) {
+            Some((version, rest)) => {
+                decode(version, rest).or_else(|err| decode(1, raw).map_err(|_| err))
+            }
+            None => decode(1, raw),
+        }
+    }
+
+    /// The codec the cursor is encoded and decoded with. Defaults to the codec installed with
+    /// `set_cursor_codec`, which base64 encodes with the url safe alphabet unless configured otherwise.
+    fn codec() -> &'static CursorCodec {
+        global_cursor_codec()
+    }
+
+    /// Builds the CursorType from a string produced by `to_encoded_string`.
     /// Returns a CursorError if the decoding fails.
+    ///
+    /// If the codec signs or encrypts cursors, the cursor is verified and/or decrypted first.
     fn from_encoded_string(input: &str) -> Result<Self::CursorType, CursorError> {
-        let decoded = BASE64_URL_SAFE.decode(input)?;
-        let decoded_string = String::from_utf8(decoded)?;
-        Self::new(decoded_string.as_str(), decoded_string.split(':').collect())
+        Self::from_raw_string(&Self::codec().decode(input)?)
     }
 
-    /// Builds the base64 encoded variant of the cursor.
-    /// Uses the url safe alphabet.
+    /// Builds the encoded variant of the cursor with the cursor's `codec`. By default that's base64
+    /// with the url safe alphabet.
+    ///
+    /// If the codec signs or encrypts cursors, the cursor is encrypted and/or signed.
     fn to_encoded_string(&self) -> String {
-        BASE64_URL_SAFE.encode(self.to_raw_string().as_bytes())
+        Self::codec().encode(&self.to_versioned_raw_string())
     }
 
     // ------------- GraphQLScalar implementations --------------
@@ -76,7 +126,7 @@ pub trait Cursor {
 /// For instance, to parse out an Offset cursor:
 ///
 /// ```rust
-/// use graphql_relay_helpers::{cursor_from_encoded_string, OffsetCursor};
+/// use juniper_relay_helpers::{cursor_from_encoded_string, OffsetCursor};
 ///
 /// let decoded_cursor = cursor_from_encoded_string::<OffsetCursor>("b2Zmc2V0OjE6MTA=");
 /// ```
@@ -91,6 +141,35 @@ where
     Ok(cursor)
 }
 
+/// Escapes a value so that it can be used as a single colon separated segment of a raw cursor
+/// string. Any `:` (and the `%` used as the escape character) are percent encoded.
+///
+/// Use this in your own `Cursor::to_raw_string` implementations if a segment could contain a colon,
+/// such as timestamps, and reverse it with `unescape_cursor_segment` in `Cursor::new`.
+pub fn escape_cursor_segment(value: &str) -> String {
+    value.replace('%', "%25").replace(':', "%3A")
+}
+
+/// Reverses `escape_cursor_segment`. Returns a CursorError if the segment contains an invalid
+/// escape sequence.
+pub fn unescape_cursor_segment(segment: &str) -> Result<String, CursorError> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut iter = segment.bytes();
+    while let Some(byte) = iter.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hex = [
+            iter.next().ok_or(CursorError::InvalidCursor)?,
+            iter.next().ok_or(CursorError::InvalidCursor)?,
+        ];
+        let hex = std::str::from_utf8(&hex).map_err(|_| CursorError::InvalidCursor)?;
+        bytes.push(u8::from_str_radix(hex, 16).map_err(|_| CursorError::InvalidCursor)?);
+    }
+    Ok(String::from_utf8(bytes)?)
+}
+
 /// A simple offset-based cursor.
 #[derive(Debug, GraphQLScalar, Default)]
 #[graphql(
@@ -236,6 +315,130 @@ mod tests {
         }
     }
 
+    mod segment_escaping_tests {
+        use crate::{CursorError, escape_cursor_segment, unescape_cursor_segment};
+
+        #[test]
+        fn test_escape_segment() {
+            assert_eq!(escape_cursor_segment("plain"), "plain");
+            assert_eq!(
+                escape_cursor_segment("2025-04-24T10:30:00Z"),
+                "2025-04-24T10%3A30%3A00Z"
+            );
+            assert_eq!(escape_cursor_segment("100%:"), "100%25%3A");
+        }
+
+        #[test]
+        fn test_unescape_segment() {
+            assert_eq!(
+                unescape_cursor_segment("2025-04-24T10%3A30%3A00Z").unwrap(),
+                "2025-04-24T10:30:00Z"
+            );
+            assert_eq!(unescape_cursor_segment("100%25%3A").unwrap(), "100%:");
+        }
+
+        #[test]
+        fn test_unescape_invalid_segment() {
+            assert_eq!(
+                unescape_cursor_segment("broken%3"),
+                Err(CursorError::InvalidCursor)
+            );
+            assert_eq!(
+                unescape_cursor_segment("broken%zz"),
+                Err(CursorError::InvalidCursor)
+            );
+        }
+    }
+
+    mod derived_cursor_tests {
+        use crate::{Cursor, CursorError, RelayCursor};
+
+        #[derive(Debug, RelayCursor, Eq, PartialEq)]
+        #[cursor(tag = "message", default)]
+        struct MessageCursor {
+            sent_at: String,
+            id: i32,
+        }
+
+        #[derive(Debug, RelayCursor, Eq, PartialEq)]
+        #[cursor(tag = "page", name = "PageNumberCursor", default)]
+        struct PageCursor(u32);
+
+        // `IpAddr` has no `Default`, so this only builds as long as one isn't generated:
+        #[derive(Debug, RelayCursor, Eq, PartialEq)]
+        #[cursor(tag = "host")]
+        struct HostCursor {
+            addr: std::net::IpAddr,
+        }
+
+        #[test]
+        fn test_derived_cursor_raw_string() {
+            let cursor = MessageCursor {
+                sent_at: "2025-04-24T10:30:00Z".to_string(),
+                id: 12,
+            };
+            assert_eq!(
+                cursor.to_raw_string(),
+                "message:2025-04-24T10%3A30%3A00Z:12"
+            );
+            assert_eq!(cursor.to_string(), "message:2025-04-24T10%3A30%3A00Z:12");
+            assert_eq!(PageCursor(3).to_raw_string(), "page:3");
+        }
+
+        #[test]
+        fn test_derived_cursor_round_trip() {
+            let cursor = MessageCursor {
+                sent_at: "2025-04-24T10:30:00Z".to_string(),
+                id: 12,
+            };
+            let decoded = MessageCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+            assert_eq!(decoded, cursor);
+
+            let decoded = PageCursor::from_encoded_string(&PageCursor(3).to_encoded_string());
+            assert_eq!(decoded, Ok(PageCursor(3)));
+
+            let cursor = HostCursor {
+                addr: "::1".parse().unwrap(),
+            };
+            assert_eq!(cursor.to_raw_string(), "host:%3A%3A1");
+            let decoded = HostCursor::from_encoded_string(&cursor.to_encoded_string());
+            assert_eq!(decoded, Ok(cursor));
+        }
+
+        #[test]
+        fn test_derived_cursor_default() {
+            assert_eq!(
+                MessageCursor::default(),
+                MessageCursor {
+                    sent_at: "".to_string(),
+                    id: 0
+                }
+            );
+            assert_eq!(PageCursor::default(), PageCursor(0));
+        }
+
+        #[test]
+        fn test_derived_cursor_invalid() {
+            // Wrong tag:
+            let other = PageCursor(3).to_encoded_string();
+            assert_eq!(
+                MessageCursor::from_encoded_string(&other),
+                Err(CursorError::InvalidCursor)
+            );
+
+            // Field fails to parse:
+            let bad_id = crate::OffsetCursor::new(1, None).to_encoded_string();
+            assert_eq!(
+                PageCursor::from_encoded_string(&bad_id),
+                Err(CursorError::InvalidCursor)
+            );
+
+            // Wrong number of segments:
+            let short = MessageCursor::from_input("bWVzc2FnZToxMg==");
+            assert!(short.is_err());
+        }
+    }
+
     mod string_cursor_tests {
         use crate::{Cursor, StringCursor};
 
@@ -1,12 +1,37 @@
-use crate::Cursor;
+use crate::{Cursor, CursorError};
 
 /// Trait encapsulating common parts of a Relay Edge.
 pub trait RelayEdge {
     type NodeType;
 
+    /// Whether every edge needs a cursor, as with `strict` nullability where it's a non-null
+    /// `cursor: String!`. Connections always work out the cursors of edges that need one.
+    const REQUIRES_CURSOR: bool = false;
+
     /// New type taking a Cursor implementation
     fn new(node: Self::NodeType, cursor: impl Cursor) -> Self;
 
-    /// New type taking a string cursor
+    /// New type taking a string cursor. An edge that `REQUIRES_CURSOR` and is built without one fails
+    /// to resolve its `cursor`, so prefer `try_new_raw_cursor` for those.
     fn new_raw_cursor(node: Self::NodeType, cursor: Option<String>) -> Self;
+
+    /// New type taking a string cursor, failing with `CursorError::MissingCursor` if the cursor is
+    /// missing and the edge `REQUIRES_CURSOR`.
+    fn try_new_raw_cursor(node: Self::NodeType, cursor: Option<String>) -> Result<Self, CursorError>
+    where
+        Self: Sized,
+    {
+        if Self::REQUIRES_CURSOR && cursor.is_none() {
+            return Err(CursorError::MissingCursor);
+        }
+        Ok(Self::new_raw_cursor(node, cursor))
+    }
+}
+
+/// Takes an edge apart, so that its connection can be converted with `RelayConnection::map_nodes`.
+/// Implemented by the codegen alongside `RelayEdge`.
+pub trait RelayEdgeParts: RelayEdge {
+    /// Takes the edge apart into its node and string cursor. The node is only missing on edges with
+    /// `resilient` nullability.
+    fn into_parts(self) -> (Option<Self::NodeType>, Option<String>);
 }
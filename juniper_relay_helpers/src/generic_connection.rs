@@ -0,0 +1,404 @@
+use crate::{
+    Cursor, PageInfo, RelayConnection, RelayConnectionParts, RelayEdge, RelayEdgeParts,
+    RelayNodeName,
+};
+use juniper::macros::reflect;
+use juniper::meta::MetaType;
+use juniper::{
+    ArcStr, Arguments, BoxFuture, ExecutionResult, Executor, FieldError, GraphQLType, GraphQLValue,
+    GraphQLValueAsync, Registry, ScalarValue, marker,
+};
+
+/// A Relay connection of any node type, for use in place of the types generated by `RelayConnection`,
+/// such as in generic code. Exposed to GraphQL the same way as a generated connection, with the
+/// name given by the node's `RelayNodeName`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Connection<N> {
+    pub count: i32,
+    pub edges: Vec<Edge<N>>,
+    pub page_info: PageInfo,
+}
+
+/// An edge of a `Connection<N>`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Edge<N> {
+    pub node: N,
+    pub cursor: Option<String>,
+}
+
+impl<N> RelayConnection for Connection<N> {
+    type EdgeType = Edge<N>;
+    type NodeType = N;
+
+    fn from_edges(edges: Vec<Edge<N>>, total_items: Option<i32>, page_info: PageInfo) -> Self {
+        Self {
+            count: total_items.unwrap_or_default(),
+            edges,
+            page_info,
+        }
+    }
+}
+
+impl<N> RelayConnectionParts for Connection<N> {
+    fn into_parts(self) -> (Vec<Edge<N>>, Option<i32>, PageInfo) {
+        (self.edges, Some(self.count), self.page_info)
+    }
+}
+
+impl<N> RelayEdge for Edge<N> {
+    type NodeType = N;
+
+    fn new(node: N, cursor: impl Cursor) -> Self {
+        Self {
+            node,
+            cursor: Some(cursor.to_encoded_string()),
+        }
+    }
+
+    fn new_raw_cursor(node: N, cursor: Option<String>) -> Self {
+        Self { node, cursor }
+    }
+}
+
+impl<N> RelayEdgeParts for Edge<N> {
+    fn into_parts(self) -> (Option<N>, Option<String>) {
+        (Some(self.node), self.cursor)
+    }
+}
+
+fn unknown_field<S>(field_name: &str, type_name: &str) -> FieldError<S> {
+    FieldError::from(format!(
+        "Field `{field_name}` not found on type `{type_name}`"
+    ))
+}
+
+/// The GraphQL name of the node, for the descriptions of its connection and edge.
+fn node_name<N, S>() -> ArcStr
+where
+    N: GraphQLType<S, TypeInfo = ()>,
+    S: ScalarValue,
+{
+    N::name(&()).unwrap_or_default()
+}
+
+impl<N, S> GraphQLType<S> for Connection<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + RelayNodeName,
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<ArcStr> {
+        Some(N::CONNECTION_NAME.into())
+    }
+
+    fn meta(info: &(), registry: &mut Registry<S>) -> MetaType<S> {
+        let fields = [
+            registry.field::<i32>("count", &()),
+            registry.field::<Vec<Edge<N>>>("edges", &()),
+            registry.field::<PageInfo>("pageInfo", &()),
+        ];
+        registry
+            .build_object_type::<Self>(info, &fields)
+            .description(format!("Connection type for {}.", node_name::<N, S>()))
+            .into_meta()
+    }
+}
+
+impl<N, S> GraphQLValue<S> for Connection<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + RelayNodeName,
+    S: ScalarValue,
+{
+    type Context = N::Context;
+    type TypeInfo = ();
+
+    fn type_name(&self, info: &()) -> Option<ArcStr> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &(),
+        field_name: &str,
+        _: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field_name {
+            "count" => executor.resolve_with_ctx(info, &self.count),
+            "edges" => executor.resolve(info, &self.edges),
+            "pageInfo" => executor.resolve_with_ctx(info, &self.page_info),
+            _ => Err(unknown_field(field_name, N::CONNECTION_NAME)),
+        }
+    }
+}
+
+impl<N, S> GraphQLValueAsync<S> for Connection<N>
+where
+    N: GraphQLValueAsync<S, TypeInfo = ()> + GraphQLType<S> + RelayNodeName,
+    N::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_async<'a>(
+        &'a self,
+        info: &'a (),
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        match field_name {
+            "edges" => Box::pin(executor.resolve_async(info, &self.edges)),
+            _ => Box::pin(async move { self.resolve_field(info, field_name, arguments, executor) }),
+        }
+    }
+}
+
+impl<N, S> marker::IsOutputType<S> for Connection<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + marker::IsOutputType<S> + RelayNodeName,
+    S: ScalarValue,
+{
+    fn mark() {
+        N::mark();
+    }
+}
+
+// Returning a type from a `#[graphql_object]` resolver needs juniper's `#[doc(hidden)]` reflection
+// traits, and a generic `#[graphql_object]` can't take its name from `N` to implement them for us.
+// They aren't covered by semver, which is why this module is behind the `generic-connection` feature
+// and only supported on juniper 0.17.1.
+impl<N: RelayNodeName, S> reflect::BaseType<S> for Connection<N> {
+    const NAME: reflect::Type = N::CONNECTION_NAME;
+}
+
+impl<N: RelayNodeName, S> reflect::BaseSubTypes<S> for Connection<N> {
+    const NAMES: reflect::Types = &[N::CONNECTION_NAME];
+}
+
+impl<N, S> reflect::WrappedType<S> for Connection<N> {
+    const VALUE: reflect::WrappedValue = 1;
+}
+
+impl<N, S> GraphQLType<S> for Edge<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + RelayNodeName,
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<ArcStr> {
+        Some(N::EDGE_NAME.into())
+    }
+
+    fn meta(info: &(), registry: &mut Registry<S>) -> MetaType<S> {
+        let fields = [
+            registry.field::<N>("node", &()),
+            registry.field::<Option<String>>("cursor", &()),
+        ];
+        registry
+            .build_object_type::<Self>(info, &fields)
+            .description(format!("Edge type for {}.", node_name::<N, S>()))
+            .into_meta()
+    }
+}
+
+impl<N, S> GraphQLValue<S> for Edge<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + RelayNodeName,
+    S: ScalarValue,
+{
+    type Context = N::Context;
+    type TypeInfo = ();
+
+    fn type_name(&self, info: &()) -> Option<ArcStr> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &(),
+        field_name: &str,
+        _: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field_name {
+            "node" => executor.resolve(info, &self.node),
+            "cursor" => executor.resolve_with_ctx(info, &self.cursor),
+            _ => Err(unknown_field(field_name, N::EDGE_NAME)),
+        }
+    }
+}
+
+impl<N, S> GraphQLValueAsync<S> for Edge<N>
+where
+    N: GraphQLValueAsync<S, TypeInfo = ()> + GraphQLType<S> + RelayNodeName,
+    N::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_async<'a>(
+        &'a self,
+        info: &'a (),
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        match field_name {
+            "node" => Box::pin(executor.resolve_async(info, &self.node)),
+            _ => Box::pin(async move { self.resolve_field(info, field_name, arguments, executor) }),
+        }
+    }
+}
+
+impl<N, S> marker::IsOutputType<S> for Edge<N>
+where
+    N: GraphQLType<S, TypeInfo = ()> + marker::IsOutputType<S> + RelayNodeName,
+    S: ScalarValue,
+{
+    fn mark() {
+        N::mark();
+    }
+}
+
+impl<N: RelayNodeName, S> reflect::BaseType<S> for Edge<N> {
+    const NAME: reflect::Type = N::EDGE_NAME;
+}
+
+impl<N: RelayNodeName, S> reflect::BaseSubTypes<S> for Edge<N> {
+    const NAMES: reflect::Types = &[N::EDGE_NAME];
+}
+
+impl<N, S> reflect::WrappedType<S> for Edge<N> {
+    const VALUE: reflect::WrappedValue = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Connection, Cursor, OffsetCursor, OffsetCursorProvider, PageRequest, RelayConnection,
+        RelayNodeName,
+    };
+    use juniper::{
+        EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, RootNode, Variables,
+        graphql_object, graphql_value,
+    };
+
+    #[derive(Debug, GraphQLObject, RelayNodeName, Clone, Eq, PartialEq)]
+    pub struct Planet {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, RelayNodeName, Clone, Eq, PartialEq)]
+    #[graphql(name = "Moon")]
+    pub struct Satellite {
+        name: String,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn planets() -> FieldResult<Connection<Planet>> {
+            Ok(page(&["Lumière", "Continent"]))
+        }
+
+        async fn moons() -> Connection<Satellite> {
+            page(&["Monolith"]).map_nodes(|planet: Planet| Satellite { name: planet.name })
+        }
+    }
+
+    /// Works the same for any node type, without a connection type of its own.
+    fn page<N: From<&'static str>>(names: &[&'static str]) -> Connection<N> {
+        Connection::from_nodes(
+            names.iter().map(|name| N::from(name)),
+            names.len() as i32 + 1,
+            OffsetCursorProvider::new(),
+            Some(PageRequest::new(
+                Some(names.len() as i32),
+                None::<OffsetCursor>,
+            )),
+        )
+    }
+
+    impl From<&'static str> for Planet {
+        fn from(name: &'static str) -> Self {
+            Planet {
+                name: name.to_owned(),
+            }
+        }
+    }
+
+    fn schema() -> RootNode<Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn connection_is_built_from_nodes() {
+        let conn: Connection<Planet> = page(&["Lumière", "Continent"]);
+        assert_eq!(conn.count, 3);
+        assert_eq!(conn.edges[1].node.name, "Continent");
+        assert_eq!(
+            conn.edges[1].cursor,
+            Some(OffsetCursor::new(1, None).to_encoded_string())
+        );
+        assert!(conn.page_info.has_next_page);
+    }
+
+    #[test]
+    fn connection_resolves() {
+        let (value, errors) = juniper::execute_sync(
+            "{ planets { count edges { cursor node { name } } pageInfo { hasNextPage } } }",
+            None,
+            &schema(),
+            &Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            value,
+            graphql_value!({
+                "planets": {
+                    "count": 3,
+                    "edges": [
+                        { "cursor": "b2Zmc2V0OjA=", "node": { "name": "Lumière" } },
+                        { "cursor": "b2Zmc2V0OjE=", "node": { "name": "Continent" } },
+                    ],
+                    "pageInfo": { "hasNextPage": true },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn types_are_named_after_the_node() {
+        let (value, errors) = juniper::execute_sync(
+            r#"{
+                connection: __type(name: "MoonConnection") { description fields { name } }
+                edge: __type(name: "MoonEdge") { description fields { name } }
+            }"#,
+            None,
+            &schema(),
+            &Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            value,
+            graphql_value!({
+                "connection": {
+                    "description": "Connection type for Moon.",
+                    "fields": [{ "name": "count" }, { "name": "edges" }, { "name": "pageInfo" }],
+                },
+                "edge": {
+                    "description": "Edge type for Moon.",
+                    "fields": [{ "name": "node" }, { "name": "cursor" }],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn node_names_are_derived() {
+        assert_eq!(Planet::CONNECTION_NAME, "PlanetConnection");
+        assert_eq!(Planet::EDGE_NAME, "PlanetEdge");
+        assert_eq!(Satellite::CONNECTION_NAME, "MoonConnection");
+        assert_eq!(Satellite::EDGE_NAME, "MoonEdge");
+    }
+}
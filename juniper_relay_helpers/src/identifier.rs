@@ -61,6 +61,8 @@ where
         }
     }
 
+    /// Encodes the identifier as url safe base64. Unlike cursors this doesn't follow the installed
+    /// `CursorCodec`, so identifiers that have already been handed out stay valid if it changes.
     pub fn to_encoded_string(&self) -> String {
         BASE64_URL_SAFE.encode(self.to_string())
     }
@@ -0,0 +1,410 @@
+use crate::cursor_errors::CursorError;
+use crate::cursor_provider::page_info_from_request;
+use crate::{
+    Cursor, CursorProvider, PageInfo, PageRequest, PaginationMetadata, escape_cursor_segment,
+    unescape_cursor_segment,
+};
+use juniper::GraphQLScalar;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A sort key that can be stored in a `KeysetCursor`, made up of one or more values.
+///
+/// Implemented for tuples of up to six values that are `Display + FromStr`, so a key sorted on
+/// `(created_at, id)` can be used as-is. Single value keys are written as a one item tuple: `(id,)`.
+pub trait KeysetKey: Sized {
+    /// Serialize the key into its segments, one per value.
+    fn to_segments(&self) -> Vec<String>;
+
+    /// Rebuild the key from its segments. Return a CursorError if there are the wrong number of
+    /// segments or any of them fail to parse.
+    fn from_segments(segments: &[String]) -> Result<Self, CursorError>;
+}
+
+macro_rules! impl_keyset_key_for_tuple {
+    ($len:expr => $($name:ident: $idx:tt),+) => {
+        impl<$($name),+> KeysetKey for ($($name,)+)
+        where
+            $($name: Display + FromStr),+
+        {
+            fn to_segments(&self) -> Vec<String> {
+                vec![$(self.$idx.to_string()),+]
+            }
+
+            fn from_segments(segments: &[String]) -> Result<Self, CursorError> {
+                if segments.len() != $len {
+                    return Err(CursorError::InvalidCursor);
+                }
+                Ok(($(
+                    segments[$idx]
+                        .parse::<$name>()
+                        .map_err(|_| CursorError::InvalidCursor)?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_keyset_key_for_tuple!(1 => A: 0);
+impl_keyset_key_for_tuple!(2 => A: 0, B: 1);
+impl_keyset_key_for_tuple!(3 => A: 0, B: 1, C: 2);
+impl_keyset_key_for_tuple!(4 => A: 0, B: 1, C: 2, D: 3);
+impl_keyset_key_for_tuple!(5 => A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_keyset_key_for_tuple!(6 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+/// Cursor that holds the sort key of an item, for keyset (or "seek") pagination.
+///
+/// Rather than remembering a position in the result set, the cursor remembers the values of the
+/// columns the set is ordered by, so the next page can be fetched with a query like
+/// `WHERE (created_at, id) > ($1, $2)`. Unlike offsets, this stays correct when rows are added or
+/// removed while paginating, and doesn't get slower the further through the set you go.
+#[derive(Debug, GraphQLScalar, Default, Clone, Eq, PartialEq)]
+#[graphql(
+    name = "KeysetCursor",
+    to_output_with = Self::to_output,
+    from_input_with = Self::from_input
+)]
+pub struct KeysetCursor {
+    /// The serialized values of the sort key, in order.
+    pub values: Vec<String>,
+}
+
+impl KeysetCursor {
+    /// Builds a cursor from a sort key.
+    pub fn from_key(key: &impl KeysetKey) -> Self {
+        KeysetCursor {
+            values: key.to_segments(),
+        }
+    }
+
+    /// Decodes the cursor back into a typed sort key.
+    pub fn key<K: KeysetKey>(&self) -> Result<K, CursorError> {
+        K::from_segments(&self.values)
+    }
+}
+
+impl Cursor for KeysetCursor {
+    type CursorType = KeysetCursor;
+
+    fn to_raw_string(&self) -> String {
+        let mut raw = "keyset".to_string();
+        for value in &self.values {
+            raw.push(':');
+            raw.push_str(&escape_cursor_segment(value));
+        }
+        raw
+    }
+
+    fn new(_raw: &str, parts: Vec<&str>) -> Result<Self::CursorType, CursorError> {
+        if parts.len() < 2 || parts[0] != "keyset" {
+            return Err(CursorError::InvalidCursor);
+        }
+
+        let values = parts[1..]
+            .iter()
+            .map(|part| unescape_cursor_segment(part))
+            .collect::<Result<Vec<String>, CursorError>>()?;
+
+        Ok(KeysetCursor { values })
+    }
+}
+
+impl Display for KeysetCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_raw_string())
+    }
+}
+
+/// The typed sort keys decoded from the `after` and `before` cursors of a `PageRequest`, ready
+/// to be turned into the `WHERE` clause of a keyset query.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct KeysetBounds<K> {
+    /// Items must sort after this key.
+    pub after: Option<K>,
+
+    /// Items must sort before this key.
+    pub before: Option<K>,
+}
+
+impl<K: KeysetKey> KeysetBounds<K> {
+    /// Decodes the `after` and `before` cursors of the request into their sort keys. Returns a
+    /// CursorError if either of them isn't a valid `KeysetCursor` for this key type.
+    pub fn from_page_request(page_request: &PageRequest) -> Result<Self, CursorError> {
+        Ok(KeysetBounds {
+            after: page_request
+                .parsed_cursor::<KeysetCursor>()?
+                .map(|c| c.key())
+                .transpose()?,
+            before: page_request
+                .parsed_before_cursor::<KeysetCursor>()?
+                .map(|c| c.key())
+                .transpose()?,
+        })
+    }
+}
+
+// -------------- KeysetCursorProvider ---------------
+
+/// Cursor provider that builds a `KeysetCursor` for each item from its sort key.
+///
+/// Takes a function that reads the sort key off the node:
+///
+/// ```
+/// use juniper_relay_helpers::KeysetCursorProvider;
+///
+/// struct Message {
+///     id: i32,
+///     sent_at: String,
+/// }
+///
+/// let provider = KeysetCursorProvider::new(|message: &Message| (message.sent_at.clone(), message.id));
+/// ```
+///
+/// Use `KeysetBounds::from_page_request` to decode the cursors the client sends back.
+///
+/// Without offsets there's no way of knowing where the page sits within the whole result set, so
+/// the `PageInfo` is worked out from the request itself. A full page is assumed to have more items
+/// following it (or preceding it, when paginating backwards), so the final page of a set that divides
+/// exactly into pages will still report `has_next_page`.
+pub struct KeysetCursorProvider<F> {
+    key_fn: F,
+}
+
+impl<F> KeysetCursorProvider<F> {
+    /// Creates the provider from the function that reads the sort key off a node.
+    pub fn new(key_fn: F) -> Self {
+        KeysetCursorProvider { key_fn }
+    }
+}
+
+impl<N, K, F> CursorProvider<N> for KeysetCursorProvider<F>
+where
+    K: KeysetKey,
+    F: Fn(&N) -> K,
+{
+    fn get_cursor_for_item(
+        &self,
+        _metadata: &PaginationMetadata,
+        _item_idx: i32,
+        item: &N,
+    ) -> impl Cursor {
+        KeysetCursor::from_key(&(self.key_fn)(item))
+    }
+
+    fn get_page_info(&self, metadata: &PaginationMetadata, items: &[N]) -> PageInfo {
+        page_info_from_request(metadata, items, |_, item| {
+            KeysetCursor::from_key(&(self.key_fn)(item))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod keyset_cursor_tests {
+        use crate::{Cursor, CursorError, KeysetCursor};
+
+        #[test]
+        fn test_keyset_cursor_raw_string() {
+            let cursor = KeysetCursor::from_key(&("2025-04-24T10:30:00Z".to_string(), 12));
+            assert_eq!(cursor.to_string(), "keyset:2025-04-24T10%3A30%3A00Z:12");
+        }
+
+        #[test]
+        fn test_keyset_cursor_round_trip() {
+            let cursor = KeysetCursor::from_key(&("2025-04-24T10:30:00Z".to_string(), 12));
+            let decoded = KeysetCursor::from_encoded_string(&cursor.to_encoded_string()).unwrap();
+            assert_eq!(decoded, cursor);
+
+            let (sent_at, id) = decoded.key::<(String, i32)>().unwrap();
+            assert_eq!(sent_at, "2025-04-24T10:30:00Z");
+            assert_eq!(id, 12);
+        }
+
+        #[test]
+        fn test_keyset_cursor_wrong_key_type() {
+            let cursor = KeysetCursor::from_key(&("not-a-number".to_string(), 12));
+            assert_eq!(cursor.key::<(i32, i32)>(), Err(CursorError::InvalidCursor));
+            assert_eq!(cursor.key::<(i32,)>(), Err(CursorError::InvalidCursor));
+        }
+
+        #[test]
+        fn test_keyset_cursor_wrong_tag() {
+            let cursor = KeysetCursor::from_encoded_string("b2Zmc2V0OjE6MTA=");
+            assert_eq!(cursor, Err(CursorError::InvalidCursor));
+        }
+    }
+
+    mod keyset_cursor_provider_tests {
+        use crate::{
+            Cursor, CursorProvider, KeysetBounds, KeysetCursor, KeysetCursorProvider, PageRequest,
+            PaginationMetadata,
+        };
+
+        #[derive(Debug, Clone)]
+        struct Message {
+            id: i32,
+            sent_at: String,
+        }
+
+        fn data() -> Vec<Message> {
+            vec![
+                Message {
+                    id: 7,
+                    sent_at: "2025-04-24T10:30:00Z".to_owned(),
+                },
+                Message {
+                    id: 3,
+                    sent_at: "2025-04-24T10:31:00Z".to_owned(),
+                },
+            ]
+        }
+
+        fn provider() -> KeysetCursorProvider<impl Fn(&Message) -> (String, i32)> {
+            KeysetCursorProvider::new(|m: &Message| (m.sent_at.clone(), m.id))
+        }
+
+        #[test]
+        fn test_cursor_built_from_item() {
+            let provider = provider();
+            let data = data();
+            let cursor = provider.get_cursor_for_item(
+                &PaginationMetadata {
+                    total_count: Some(2),
+                    has_more: None,
+                    page_request: None,
+                },
+                1,
+                &data[1],
+            );
+            assert_eq!(
+                cursor.to_encoded_string(),
+                KeysetCursor::from_key(&("2025-04-24T10:31:00Z".to_string(), 3))
+                    .to_encoded_string()
+            );
+        }
+
+        #[test]
+        fn test_page_info_no_request() {
+            let pi = provider().get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(2),
+                    has_more: None,
+                    page_request: None,
+                },
+                &data(),
+            );
+            assert!(!pi.has_prev_page);
+            assert!(!pi.has_next_page);
+            assert_eq!(
+                pi.start_cursor,
+                Some(
+                    KeysetCursor::from_key(&("2025-04-24T10:30:00Z".to_string(), 7))
+                        .to_encoded_string()
+                )
+            );
+            assert_eq!(
+                pi.end_cursor,
+                Some(
+                    KeysetCursor::from_key(&("2025-04-24T10:31:00Z".to_string(), 3))
+                        .to_encoded_string()
+                )
+            );
+        }
+
+        #[test]
+        fn test_page_info_forwards() {
+            let pi = provider().get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    has_more: None,
+                    page_request: Some(PageRequest::new(
+                        Some(2),
+                        Some(KeysetCursor::from_key(&(
+                            "2025-04-24T10:29:00Z".to_string(),
+                            1,
+                        ))),
+                    )),
+                },
+                &data(),
+            );
+            assert!(pi.has_prev_page);
+            assert!(pi.has_next_page);
+
+            // Fewer items than asked for means the end of the set:
+            let pi = provider().get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    has_more: None,
+                    page_request: Some(PageRequest::new(
+                        Some(5),
+                        Some(KeysetCursor::from_key(&(
+                            "2025-04-24T10:29:00Z".to_string(),
+                            1,
+                        ))),
+                    )),
+                },
+                &data(),
+            );
+            assert!(pi.has_prev_page);
+            assert!(!pi.has_next_page);
+        }
+
+        #[test]
+        fn test_page_info_backwards() {
+            let pi = provider().get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    has_more: None,
+                    page_request: Some(PageRequest::new_backward(Some(2), None::<KeysetCursor>)),
+                },
+                &data(),
+            );
+            assert!(pi.has_prev_page);
+            assert!(!pi.has_next_page);
+
+            let pi = provider().get_page_info(
+                &PaginationMetadata {
+                    total_count: Some(10),
+                    has_more: None,
+                    page_request: Some(PageRequest::new_backward(
+                        Some(5),
+                        Some(KeysetCursor::from_key(&(
+                            "2025-04-24T10:32:00Z".to_string(),
+                            1,
+                        ))),
+                    )),
+                },
+                &data(),
+            );
+            assert!(!pi.has_prev_page);
+            assert!(pi.has_next_page);
+        }
+
+        #[test]
+        fn test_bounds_from_page_request() {
+            let pr = PageRequest::from_arguments(
+                Some(10),
+                Some(KeysetCursor::from_key(&(
+                    "2025-04-24T10:29:00Z".to_string(),
+                    1,
+                ))),
+                None,
+                Some(KeysetCursor::from_key(&(
+                    "2025-04-24T10:40:00Z".to_string(),
+                    9,
+                ))),
+            );
+            let bounds = KeysetBounds::<(String, i32)>::from_page_request(&pr).unwrap();
+            assert_eq!(bounds.after, Some(("2025-04-24T10:29:00Z".to_string(), 1)));
+            assert_eq!(bounds.before, Some(("2025-04-24T10:40:00Z".to_string(), 9)));
+
+            let empty = KeysetBounds::<(String, i32)>::from_page_request(&PageRequest::new(
+                Some(10),
+                None::<KeysetCursor>,
+            ))
+            .unwrap();
+            assert_eq!(empty.after, None);
+            assert_eq!(empty.before, None);
+        }
+    }
+}
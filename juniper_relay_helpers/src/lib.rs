@@ -19,12 +19,15 @@
 //! # use juniper_relay_helpers::PageInfo;
 //!
 //! #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
-//! struct PlayableCharacter {
+//! pub struct PlayableCharacter {
 //!     pub name: String,
 //!     pub theme_song: String,
 //! }
+//! ```
+//!
+//! The generated structs - written out here to show the full code:
 //!
-//! // Generated structs - written out here to show the full code::
+//! ```nocompile
 //! #[derive(GraphQLObject)]
 //! struct PlayableCharacterRelayConnection {
 //!     count: i32,
@@ -34,7 +37,7 @@
 //!
 //! #[derive(GraphQLObject)]
 //! struct PlayableCharacterRelayEdge {
-//!     cursor: String,
+//!     cursor: Option<String>,
 //!     node: PlayableCharacter,
 //! }
 //!
@@ -45,12 +48,12 @@
 //! ```graphql
 //! type PlayableCharacterConnection {
 //!     count: Int!
-//!     edges: [PlayableCharacterEdge]!
+//!     edges: [PlayableCharacterEdge!]!
 //!     pageInfo: PageInfo!
 //! }
 //!
 //! type PlayableCharacterEdge {
-//!     cursor: String!
+//!     cursor: String
 //!     node: PlayableCharacter!
 //! }
 //! ```
@@ -58,6 +61,291 @@
 //! **Notes**:
 //! - The struct has `RelayConnection` and `RelayEdge` as the suffix to help avoid collisions with your code.
 //! - GraphQL types have `Connection` and `Edge` as the suffix to conform to the spec.
+//! - The generated structs have the same visibility as the node struct.
+//! - Nothing else is added to your module, so import the `RelayConnection` and `RelayEdge` traits to
+//!   use their constructors.
+//!
+//! ## Nullability
+//!
+//! The nullability of the generated fields can be changed to match the schema your clients expect,
+//! with `#[relay(nullability = "...")]`:
+//!
+//! ```rust
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers_codegen::RelayConnection;
+//! #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(nullability = "strict")]
+//! pub struct Weapon {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! | Profile      | `edges`     | `node`  | `cursor`  |
+//! |--------------|-------------|---------|-----------|
+//! | _(default)_  | `[Edge!]!`  | `Node!` | `String`  |
+//! | `strict`     | `[Edge!]!`  | `Node!` | `String!` |
+//! | `resilient`  | `[Edge]`    | `Node`  | `String`  |
+//!
+//! `strict` follows the Relay spec exactly. `resilient` lets a node that fails to resolve be nulled
+//! out without taking the whole connection with it. In Rust, `resilient` edges are
+//! `Option<Vec<Option<Edge>>>` and nodes are `Option<Node>`. The `RelayConnection` constructors work
+//! the same for every profile.
+//!
+//! ## Naming
+//!
+//! The GraphQL names of the generated types follow the node's GraphQL name, so a node with
+//! `#[graphql(name = "Hero")]` gets `HeroConnection` and `HeroEdge`. The Rust and GraphQL names and
+//! descriptions can also be set directly:
+//!
+//! ```rust
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers_codegen::RelayConnection;
+//! #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(
+//!     connection_type = WeaponPage,
+//!     edge_type = WeaponPageEdge,
+//!     connection_name = "WeaponPage",
+//!     edge_name = "WeaponPageEdge",
+//!     connection_description = "A page of weapons.",
+//!     edge_description = "A weapon within a page."
+//! )]
+//! pub struct Weapon {
+//!     pub name: String,
+//! }
+//! ```
+//!
+//! ## Edge data
+//!
+//! Where the edge carries data of its own, such as the members of a party, derive `RelayConnection`
+//! on a struct holding that data and name the node with `#[relay(node = ...)]`. Its fields are added
+//! to the generated edge, and the connection and edges are built from `(node, edge data)` pairs:
+//!
+//! ```rust
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers::{NodeCursorProvider, RelayConnection, StringCursor};
+//! #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq)]
+//! pub struct Character {
+//!     pub name: String,
+//! }
+//!
+//! #[derive(Debug, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(node = Character)]
+//! pub struct PartyMembership {
+//!     /// When the character joined the party.
+//!     pub joined_at: String,
+//! }
+//!
+//! let members = vec![(
+//!     Character { name: "Gustave".to_string() },
+//!     PartyMembership { joined_at: "Act I".to_string() },
+//! )];
+//! let connection = PartyMembershipRelayConnection::new(
+//!     &members,
+//!     1,
+//!     NodeCursorProvider::new(|(character, _): &(Character, PartyMembership)| {
+//!         StringCursor::new(character.name.clone())
+//!     }),
+//!     None,
+//! );
+//! assert_eq!(connection.edges[0].joined_at, "Act I");
+//! ```
+//!
+//! The `#[relay(...)]` naming options above are useful here, as the types are otherwise named after
+//! the edge data struct, ie `PartyMembershipConnection`.
+//!
+//! ## Nodes field
+//!
+//! `#[relay(nodes_field)]` adds a `nodes` field to the connection, as some APIs have alongside `edges`
+//! so that clients who don't need the cursors can skip the edges:
+//!
+//! ```graphql
+//! type PlayableCharacterConnection {
+//!     count: Int!
+//!     edges: [PlayableCharacterEdge!]!
+//!     nodes: [PlayableCharacter!]!
+//!     pageInfo: PageInfo!
+//! }
+//! ```
+//!
+//! The nodes are resolved from the edges, so aren't stored twice.
+//!
+//! ## Connection fields
+//!
+//! Fields such as totals and summaries can be added to the connection alongside `count`, `edges` and
+//! `pageInfo` with `connection_fields`:
+//!
+//! ```rust
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers::{OffsetCursorProvider, RelayConnection};
+//! #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(connection_fields(
+//!     /// The number of chroma across every expedition, not just this page.
+//!     total_chroma: i32,
+//! ))]
+//! pub struct Expedition {
+//!     pub number: i32,
+//! }
+//!
+//! # let expeditions = vec![Expedition { number: 33 }];
+//! let connection = ExpeditionRelayConnection {
+//!     total_chroma: 1200,
+//!     ..ExpeditionRelayConnection::new(&expeditions, 1, OffsetCursorProvider::new(), None)
+//! };
+//! ```
+//!
+//! The fields are `Default` when the connection is built by `new` and the other constructors, so
+//! their types need to implement `Default`.
+//!
+//! ## Resolvers and context
+//!
+//! Fields that have to be worked out when they're queried, such as counts that depend on the
+//! permissions of the viewer, can be added with `connection_resolvers` and `edge_resolvers`. Each
+//! one names the function that resolves it, which is passed the connection or edge. Set the juniper
+//! `context` of the generated types to have it passed to the functions too, and mark a resolver
+//! `async` when its function is:
+//!
+//! ```rust
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers::RelayConnection;
+//! pub struct Database;
+//! impl juniper::Context for Database {}
+//!
+//! #[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(
+//!     context = Database,
+//!     connection_resolvers(
+//!         /// How many of the characters the viewer can see.
+//!         visible_count: i32 = CharacterRelayConnection::count_visible,
+//!     ),
+//!     edge_resolvers(async last_seen: Option<String> = load_last_seen),
+//! )]
+//! pub struct Character {
+//!     pub name: String,
+//! }
+//!
+//! impl CharacterRelayConnection {
+//!     fn count_visible(&self, db: &Database) -> i32 {
+//!         # let _ = db;
+//!         self.edges.len() as i32
+//!     }
+//! }
+//!
+//! async fn load_last_seen(edge: &CharacterRelayEdge, db: &Database) -> Option<String> {
+//!     # let _ = (edge, db);
+//!     None
+//! }
+//! ```
+//!
+//! The functions can't share the name of the field when they're methods of the generated types, as
+//! the resolvers are added to those types too.
+//!
+//! ## Generic nodes
+//!
+//! Generic nodes get generic Connection and Edge types, with the same parameters and where clauses.
+//! A generic type can't have a single GraphQL name, so name each instantiation that's used in the
+//! schema with `instance`, alongside the GraphQL objects for the nodes themselves:
+//!
+//! ```rust
+//! # use juniper::{GraphQLObject, graphql_object};
+//! # use juniper_relay_helpers::RelayConnection;
+//! # #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq)]
+//! # pub struct Character { pub name: String }
+//! #[derive(Debug, RelayConnection, Clone, Eq, PartialEq)]
+//! #[relay(instance(type = Versioned<Character>, name = "VersionedCharacter"))]
+//! pub struct Versioned<T> {
+//!     pub version: i32,
+//!     pub value: T,
+//! }
+//!
+//! #[graphql_object(name = "VersionedCharacter")]
+//! impl Versioned<Character> {
+//!     fn version(&self) -> i32 {
+//!         self.version
+//!     }
+//!
+//!     fn value(&self) -> &Character {
+//!         &self.value
+//!     }
+//! }
+//! ```
+//!
+//! `VersionedRelayConnection<Character>` is then `VersionedCharacterConnection` in the schema, with
+//! `VersionedCharacterEdge` edges. The `connection_name` and similar options don't apply to instances.
+//!
+//! ## Unions and interfaces
+//!
+//! `RelayConnection` can be derived for a `GraphQLUnion` enum, so that a connection can mix
+//! different types of node:
+//!
+//! ```rust
+//! # use juniper::{GraphQLObject, GraphQLUnion};
+//! # use juniper_relay_helpers::RelayConnection;
+//! # #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq)]
+//! # pub struct Character { pub name: String }
+//! # #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq)]
+//! # pub struct Location { pub name: String }
+//! #[derive(Debug, GraphQLUnion, RelayConnection, Clone, Eq, PartialEq)]
+//! pub enum SearchResult {
+//!     Character(Character),
+//!     Location(Location),
+//! }
+//! ```
+//!
+//! The node type of an interface is the `{Name}Value` type generated by `#[graphql_interface]`,
+//! which can't be derived on, so use `relay_connection!` with it instead. It takes the same attributes
+//! as the derive:
+//!
+//! ```rust
+//! # use juniper::{GraphQLObject, graphql_interface};
+//! # use juniper_relay_helpers::relay_connection;
+//! #[graphql_interface(for = Character)]
+//! pub struct Named {
+//!     pub name: String,
+//! }
+//!
+//! #[derive(Debug, GraphQLObject, Clone)]
+//! #[graphql(impl = NamedValue)]
+//! pub struct Character {
+//!     pub name: String,
+//! }
+//!
+//! relay_connection! {
+//!     #[graphql(name = "Named")]
+//!     pub NamedValue
+//! }
+//! ```
+//!
+//! ## Generic Connection and Edge types
+//!
+//! Instead of generating types for each node, the library's own `Connection<N>` and `Edge<N>` can be
+//! used for any node that implements `RelayNodeName`. They can be returned from generic code, and
+//! support the same `RelayConnection` constructors:
+//!
+//! ```rust
+//! # #[cfg(feature = "generic-connection")] {
+//! # use juniper::GraphQLObject;
+//! # use juniper_relay_helpers::{Connection, OffsetCursorProvider, RelayConnection, RelayNodeName};
+//! #[derive(Debug, GraphQLObject, RelayNodeName, Clone)]
+//! pub struct Location {
+//!     pub name: String,
+//! }
+//!
+//! fn first_page<N: Clone>(nodes: &[N]) -> Connection<N> {
+//!     Connection::new(nodes, nodes.len() as i32, OffsetCursorProvider::new(), None)
+//! }
+//!
+//! let connection = first_page(&[Location { name: "Old Lumière".to_string() }]);
+//! assert_eq!(connection.edges[0].node.name, "Old Lumière");
+//! # }
+//! ```
+//!
+//! In the schema, `Connection<Location>` is `LocationConnection` and `Edge<Location>` is
+//! `LocationEdge`, with the default nullability and a non-null `count: Int!`. Don't also derive
+//! `RelayConnection` on a node that's used this way, as the GraphQL names would clash.
+//!
+//! They need the `generic-connection` feature. A generic type can't be named after its node through
+//! juniper's public API, so their GraphQL impls rely on juniper internals and the feature is only
+//! supported on juniper 0.17.1.
 //!
 //! ## Building Connection responses
 //!
@@ -66,41 +354,153 @@
 //!
 //! `RelayConnection` in particular has a big shortcut you'll want to make usage of.
 //!
-//! This is an example from the example app in the `/juniper_relay_helpers_test` folder:
+//! This is a simplified version of a resolver from the example app in the `/juniper_relay_helpers_test` folder:
 //!
 //! ```nocompile
-//! async fn locations(first: Option<i32>, after: Option<OffsetCursor>, ctx: &Context) -> FieldResult<LocationRelayConnection> {
-//!     let mut nodes = ctx.locations
-//!         .iter()
-//!         .map(|row| Location::from(row.clone()))
-//!         .collect::<Vec<Location>>();
-//!
-//!     if let Some(after) = &after {
-//!         nodes = nodes.split_off(after.offset as usize + 1);
-//!     }
-//!     if let Some(first) = first {
-//!         nodes.truncate(first as usize);
-//!     }
+//! async fn characters(
+//!     first: Option<i32>,
+//!     after: Option<StringCursor>,
+//!     ctx: &Context,
+//! ) -> FieldResult<CharacterRelayConnection> {
+//!     // Load up to `first` characters following the one in the `after` cursor:
+//!     let page = load_characters(after.as_ref(), first);
 //!
 //!     Ok(
-//!         LocationRelayConnection::new(
-//!             &nodes,
-//!             ctx.locations.len() as i32,
-//!             OffsetCursorProvider::new(),
+//!         CharacterRelayConnection::new(
+//!             &page,
+//!             ctx.characters.len() as i32,
+//!             NodeCursorProvider::new(|character: &Character| StringCursor::new(character.id.to_string())),
 //!             Some(PageRequest::new(first, after))
 //!         )
 //!     )
 //! }
 //! ```
-//! The `LocationRelayConnection::new` method takes the following arguments:
+//! The `CharacterRelayConnection::new` method takes the following arguments:
 //!
 //! - The nodes to include in the connection
 //! - The total count of _all_ results in this query resolver.
 //! - A cursor provider to generate cursors for the edges and `PageInfo`.
-//! - A `PageRequest` to generate the pagination info from, using the `first` and `after`.
+//! - A `PageRequest` to generate the pagination info from, using the `first`, `after`, `last` and `before`.
 //!
 //! With that, it can build up the entire response to the client with correct pagination and cursors.
 //!
+//! `new` clones each node into its edge. Owned nodes can be moved in instead with `from_nodes`, which
+//! takes anything that can be iterated over, or with `into_connection` from the `IntoConnection`
+//! trait:
+//!
+//! ```nocompile
+//! let page: Vec<Character> = load_characters(after.as_ref(), first);
+//! let connection: CharacterRelayConnection =
+//!     page.into_connection(total, provider, Some(PageRequest::new(first, after)));
+//! ```
+//!
+//! Neither needs the node to implement `Clone`, although the generated types derive it by default.
+//! Use `#[relay(derive(...))]` to pick the derives for nodes that can't be cloned.
+//!
+//! ## Connections without a total count
+//!
+//! Counting every row can cost more than fetching the page itself. Instead, fetch one more node than
+//! was asked for and use `NullableCountConnection::new_overfetched`. It trims off the extra node and
+//! uses it to work out `hasNextPage`, leaving the `count` of the connection empty. It's only there for
+//! connections with `#[relay(nullable_count)]`, so that `count` is a nullable `Int` rather than `Int!`:
+//!
+//! ```nocompile
+//! #[derive(GraphQLObject, RelayConnection)]
+//! #[relay(nullable_count)]
+//! struct Character { ... }
+//!
+//! // SELECT ... LIMIT first + 1
+//! let rows = load_characters(after.as_ref(), first.map(|first| first + 1));
+//!
+//! Ok(CharacterRelayConnection::new_overfetched(
+//!     &rows,
+//!     NodeCursorProvider::new(|character: &Character| StringCursor::new(character.id.to_string())),
+//!     PageRequest::new(first, after),
+//! )?)
+//! ```
+//!
+//! When paginating backwards with `last`, fetch `last + 1` nodes in order, so the extra one comes first.
+//! Offset cursors for `last` are counted back from the end of the result set, so they need a `before`
+//! cursor to count back from instead - without one, `new_overfetched` returns
+//! `CursorError::TotalCountRequired`.
+//!
+//! ## Only building what's selected
+//!
+//! A query that only asks for `edges { node { name } }` doesn't need the total count, the edge cursors
+//! or the `PageInfo`. Pass a `ConnectionSelection` built from the resolver's look-ahead to
+//! `RelayConnection::new_for_selection` and those are only worked out when they're selected. The total
+//! count is passed as a closure, so the count query is skipped too:
+//!
+//! ```nocompile
+//! #[graphql_object(context = Context, scalar = DefaultScalarValue)]
+//! impl QueryRoot {
+//!     async fn characters(
+//!         first: Option<i32>,
+//!         after: Option<StringCursor>,
+//!         executor: &Executor<'_, '_, Context>,
+//!     ) -> FieldResult<CharacterRelayConnection> {
+//!         let ctx = executor.context();
+//!         let page = load_characters(after.as_ref(), first);
+//!
+//!         Ok(CharacterRelayConnection::new_for_selection(
+//!             &page,
+//!             || count_characters(ctx),
+//!             NodeCursorProvider::new(|character: &Character| StringCursor::new(character.id.to_string())),
+//!             Some(PageRequest::new(first, after)),
+//!             ConnectionSelection::from_look_ahead(&executor.look_ahead()),
+//!         ))
+//!     }
+//! }
+//! ```
+//!
+//! ## Connections from in-memory arrays
+//!
+//! When all of the items are already in memory, don't slice them up by hand: `connection_from_array`
+//! picks out the page following the Relay spec's pagination algorithm and returns the populated
+//! connection, with `OffsetCursor`s for the edges:
+//!
+//! ```nocompile
+//! async fn locations(
+//!     first: Option<i32>,
+//!     after: Option<OffsetCursor>,
+//!     last: Option<i32>,
+//!     before: Option<OffsetCursor>,
+//!     ctx: &Context,
+//! ) -> FieldResult<LocationRelayConnection> {
+//!     let nodes = ctx.locations.iter().map(|row| Location::from(row.clone())).collect::<Vec<_>>();
+//!
+//!     Ok(connection_from_array(
+//!         &nodes,
+//!         &PageRequest::from_arguments(first, after, last, before),
+//!     )?)
+//! }
+//! ```
+//!
+//! If only part of the array has been loaded, `connection_from_array_slice` takes that slice, where
+//! it starts and the length of the whole array instead.
+//!
+//! ## Mapping connections between node types
+//!
+//! A connection can be built from the rows loaded from the database and then converted into one of the
+//! types exposed in the schema with `map_nodes`. The page info, `count` and edge cursors are kept.
+//! `try_map_nodes` stops at the first conversion that fails, and `map_nodes_async` awaits each
+//! conversion in turn:
+//!
+//! ```nocompile
+//! let rows: CharacterRowRelayConnection = connection_from_array(&ctx.characters, &page_request)?;
+//!
+//! let characters: CharacterRelayConnection = rows
+//!     .map_nodes_async(|row| async move { ctx.character_details(row).await })
+//!     .await;
+//! ```
+//!
+//! The error type of `try_map_nodes` needs to convert from `CursorError`, as `FieldError` does. It's
+//! the only way to map into a connection with `strict` nullability, whose edges can't be built
+//! without a cursor: an edge that's missing one fails with `CursorError::MissingCursor`.
+//!
+//! Connections written by hand can be mapped too, by implementing `RelayConnectionParts` for the
+//! connection and `RelayEdgeParts` for its edge.
+//!
 //! Naturally, you can also manually build up responses yourself and make use of the pagination
 //! primitives that the generated code uses and provides.
 //!
@@ -127,16 +527,32 @@
 //! You can either manually build this object up yourself or if you use an implementation of `CursorProvider`
 //! it can build this information for you.
 //!
+//! Older versions of this library named `hasPreviousPage` as `hasPrevPage`. Enable the
+//! `has-prev-page-alias` feature to also expose `hasPrevPage` as a deprecated field while clients
+//! migrate. The Rust field is still `PageInfo::has_prev_page`.
+//!
 //! ## Page Request
 //!
-//! Pagination requests in Relay usually are specified by a ``first`` and ``after`` argument.
+//! Pagination requests in Relay usually are specified by a ``first`` and ``after`` argument, or
+//! a ``last`` and ``before`` argument when paginating backwards through the results.
 //! This library provides a `PageRequest` struct to help with this.
 //!
 //! ```
-//! use juniper_relay_helpers::{PageRequest, StringCursor};
+//! use juniper_relay_helpers::{OffsetCursor, PageRequest, StringCursor};
 //! #
 //! # fn page_request() {
-//! let page_request = PageRequest::new(Some(10), Some(StringCursor::new("my-cursor")));
+//! let page_request = PageRequest::new(Some(10), Some(StringCursor::new("my-cursor".to_string())));
+//!
+//! // Backwards:
+//! let backward_request = PageRequest::new_backward(Some(20), Some(StringCursor::new("my-cursor".to_string())));
+//!
+//! // Or straight from all four arguments of a query resolver:
+//! let any_request = PageRequest::from_arguments(
+//!     Some(10),
+//!     None::<OffsetCursor>,
+//!     None,
+//!     Some(OffsetCursor::new(20, None)),
+//! );
 //! # }
 //! ```
 //!
@@ -155,7 +571,7 @@
 //! # use juniper_relay_helpers::{cursor_from_encoded_string, Cursor, OffsetCursor};
 //! #
 //! # fn cursors() {
-//! let cursor = OffsetCursor { offset: 1, first: 10 };
+//! let cursor = OffsetCursor { offset: 1, first: Some(10) };
 //!
 //! // Encode the cursor into a string of format "offset:1:10"
 //! let cursor_string = cursor.to_raw_string();
@@ -172,6 +588,88 @@
 //!
 //! Implementing your own cursor is as simple as implementing the `Cursor` trait.
 //!
+//! Or, if your cursor is a struct made up of fields that are `FromStr + Display`, use the
+//! `RelayCursor` derive macro to generate the `Cursor`, `Display` and `GraphQLScalar` implementations
+//! for you, plus `Default` with `#[cursor(default)]`:
+//!
+//! ```
+//! use juniper_relay_helpers::{Cursor, RelayCursor};
+//!
+//! #[derive(Debug, RelayCursor)]
+//! #[cursor(tag = "message")]
+//! pub struct MessageCursor {
+//!     pub sent_at: String,
+//!     pub id: i32,
+//! }
+//!
+//! let cursor = MessageCursor { sent_at: "2025-04-24T10:30:00Z".to_string(), id: 12 };
+//!
+//! // Colons in the fields are escaped, giving "message:2025-04-24T10%3A30%3A00Z:12"
+//! let cursor_string = cursor.to_raw_string();
+//! ```
+//!
+//! ## Versioned cursors
+//!
+//! Cursors carry a format version, `Cursor::VERSION`, which defaults to 1. When the shape of a cursor
+//! changes, bump its version and register a decoder for the previous version in `Cursor::upgraders`
+//! (or with `upgrade(version = 1, with = ...)` on the derive). Cursors issued before the change are
+//! then upgraded into the current type by `cursor_from_encoded_string` and
+//! `PageRequest::parsed_cursor`, rather than being rejected. See `CursorUpgraders` for an example.
+//!
+//! ## Signed and encrypted cursors
+//!
+//! By default cursors are only base64 encoded, so a client can decode one, read it, change it and send
+//! it back. To stop that, install a `CursorSigner` and/or a `CursorCipher` at startup:
+//!
+//! - `CursorSigner` signs every cursor with HMAC-SHA256. Cursors with a missing or invalid signature are
+//!   rejected with `CursorError::InvalidCursorSignature`.
+//! - `CursorCipher` encrypts every cursor with XChaCha20-Poly1305, so internal keys and offsets can't be
+//!   read either. Cursors that can't be decrypted are rejected with `CursorError::CursorDecryptionFailed`.
+//!
+//! Both happen as part of `Cursor::to_encoded_string` and `Cursor::from_encoded_string`, so they apply
+//! to every cursor type, including when Juniper parses a cursor argument.
+//!
+//! ```
+//! use juniper_relay_helpers::{
+//!     set_cursor_cipher, set_cursor_signer, CursorCipher, CursorEncryptionKey, CursorSigner,
+//!     CursorSigningKey,
+//! };
+//!
+//! # fn startup(encryption_key: [u8; 32]) {
+//! let signer = CursorSigner::new(CursorSigningKey::new("2025-05", "secret-from-your-config"))
+//!     // Cursors signed with an older key are still accepted while clients move over:
+//!     .with_verification_key(CursorSigningKey::new("2025-04", "previous-secret-from-your-config"));
+//! set_cursor_signer(signer).expect("cursor signer already installed");
+//!
+//! let cipher = CursorCipher::new(CursorEncryptionKey::new("2025-05", encryption_key));
+//! set_cursor_cipher(cipher).expect("cursor cipher already installed");
+//! # }
+//! ```
+//!
+//! Encryption is authenticated, so a cipher on its own also stops cursors being forged. The key ids are
+//! stored in the cursor in the clear.
+//!
+//! ## Cursor codecs
+//!
+//! How cursors are turned into strings is controlled by a `CursorCodec`: the raw string is optionally
+//! compressed, encrypted and signed, and then encoded to text. By default that's base64 with the url
+//! safe alphabet and `=` padding. Install a codec with `set_cursor_codec` to change it, for instance to
+//! drop the padding if a gateway strips `=` from query strings. The installed codec is process-global,
+//! so it applies to every schema in the process:
+//!
+//! ```
+//! use juniper_relay_helpers::{set_cursor_codec, CursorCodec, TextEncoding};
+//!
+//! # fn startup() {
+//! set_cursor_codec(CursorCodec::new().with_text_encoding(TextEncoding::UrlSafeNoPad))
+//!     .expect("cursor codec already installed");
+//! # }
+//! ```
+//!
+//! A single cursor type can use its own codec by overriding `Cursor::codec`, or with
+//! `#[cursor(codec = ...)]` on the derive. `RelayIdentifier`s keep their url safe base64 whatever the
+//! codec, so node IDs that have already been handed out don't change.
+//!
 //! ## Cursor providers
 //!
 //! Relay requires edges and pagination info to contain cursors, which can be annoying to generate
@@ -182,11 +680,36 @@
 //!
 //! For a reference implementation, see the `OffsetCursorProvider` struct.
 //!
+//! The provider is generic over the node type, so it can read whatever it needs off each item.
+//! `NodeCursorProvider` takes a function that builds any `Cursor` from a node, such as a `StringCursor`
+//! from its ID. `KeysetCursorProvider` uses this to build cursors from the values the result set is sorted by,
+//! for keyset (or "seek") pagination:
+//!
+//! ```
+//! use juniper_relay_helpers::{KeysetBounds, KeysetCursorProvider, PageRequest};
+//! # use juniper_relay_helpers::CursorError;
+//!
+//! struct Message {
+//!     id: i32,
+//!     sent_at: String,
+//! }
+//!
+//! # fn keyset(page_request: PageRequest) -> Result<(), CursorError> {
+//! let provider = KeysetCursorProvider::new(|message: &Message| (message.sent_at.clone(), message.id));
+//!
+//! // Decode the `after` and `before` cursors back into typed keys to build the query from, ie:
+//! // WHERE (sent_at, id) > ($1, $2)
+//! let bounds = KeysetBounds::<(String, i32)>::from_page_request(&page_request)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! **Note**: remember that offset cursors are massively prone to off-by-one errors. The cursor provided
 //! to the `after` argument **means** after - if you're using database offsets or memory slices, you need to
-//! add `+ 1` to the provided offset to get the _actual_ starting point.
+//! add `+ 1` to the provided offset to get the _actual_ starting point. The cursor provided to `before`
+//! is already one past the end of the page, so can be used as the end of a slice as-is.
 //!
-//! See the example app for a more detailed example of how to handle this.
+//! `connection_from_array` and `connection_from_array_slice` take care of this for you.
 //!
 //! # Identifiers
 //!
@@ -199,9 +722,7 @@
 //! ```
 //! use std::fmt::{Display, Formatter};
 //! use std::str::FromStr;
-//! use juniper_relay_helpers::{RelayIdentifier};
-//! #
-//! use graphql_relay_helpers_codegen::IdentifierTypeDiscriminator;
+//! use juniper_relay_helpers::{IdentifierTypeDiscriminator, RelayIdentifier};
 //!
 //! # fn identifiers() {
 //! #[derive(IdentifierTypeDiscriminator)]
@@ -251,22 +772,44 @@
 
 extern crate self as juniper_relay_helpers;
 
+mod array_connection;
+mod connection_selection;
 mod connections;
+mod cursor_codec;
+mod cursor_encryption;
 mod cursor_errors;
 mod cursor_provider;
+mod cursor_signing;
+mod cursor_versions;
 mod cursors;
 mod edges;
+#[cfg(feature = "generic-connection")]
+mod generic_connection;
 mod identifier;
+mod keyset;
+mod node_name;
 mod pagination;
 
 // From other crates in the workspace:
-pub use juniper_relay_helpers_codegen::{IdentifierTypeDiscriminator, RelayConnection};
+pub use juniper_relay_helpers_codegen::{
+    IdentifierTypeDiscriminator, RelayConnection, RelayCursor, RelayNodeName, relay_connection,
+};
 
 // From this crate:
+pub use array_connection::*;
+pub use connection_selection::*;
 pub use connections::*;
+pub use cursor_codec::*;
+pub use cursor_encryption::*;
 pub use cursor_errors::*;
 pub use cursor_provider::*;
+pub use cursor_signing::*;
+pub use cursor_versions::*;
 pub use cursors::*;
 pub use edges::*;
+#[cfg(feature = "generic-connection")]
+pub use generic_connection::*;
 pub use identifier::*;
+pub use keyset::*;
+pub use node_name::*;
 pub use pagination::*;
@@ -0,0 +1,11 @@
+/// Gives the GraphQL names of `Connection<Self>` and `Edge<Self>`, from the `generic-connection`
+/// feature. Derive it with
+/// `#[derive(RelayNodeName)]` to get `{Name}Connection` and `{Name}Edge`, where `{Name}` is the
+/// GraphQL name of the node.
+pub trait RelayNodeName {
+    /// The GraphQL name of `Connection<Self>`, such as `CharacterConnection`.
+    const CONNECTION_NAME: &'static str;
+
+    /// The GraphQL name of `Edge<Self>`, such as `CharacterEdge`.
+    const EDGE_NAME: &'static str;
+}
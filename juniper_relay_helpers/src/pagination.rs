@@ -1,34 +1,77 @@
 use crate::cursor_errors::CursorError;
 use crate::{Cursor, cursor_from_encoded_string};
-use juniper::GraphQLObject;
+use juniper::{GraphQLObject, graphql_object};
 
 /// Represents the Relay spec pagination object
 /// <https://relay.dev/docs/guides/graphql-server-specification/>
 ///
-#[derive(Debug, GraphQLObject, Eq, PartialEq, Clone)]
-#[graphql(description = "Pagination information")]
+/// With the `has-prev-page-alias` feature enabled, `hasPreviousPage` is also exposed under its old
+/// name of `hasPrevPage` as a deprecated field, so existing clients keep working while they migrate.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
 pub struct PageInfo {
     /// Indicates whether there is a page following this current one
-    #[graphql(description = "Indicates whether there is a page following this current one")]
     pub has_next_page: bool,
 
     /// Indicates whether there is a page preceding this one
-    #[graphql(description = "Indicates whether there is a page preceding this one")]
     pub has_prev_page: bool,
 
     /// An opaque cursor that when passed to after: in a query will return the previous page of
     /// results.
-    #[graphql(
-        description = "An opaque cursor that when passed to after: in a query will return the previous page of results."
-    )]
     pub start_cursor: Option<String>,
 
     /// An opaque cursor that when passed to after: in a query will return the following page of
     /// results.
+    pub end_cursor: Option<String>,
+}
+
+/// Builds the GraphQL object for `PageInfo`, with any extra fields passed in. `#[cfg]` isn't applied
+/// to the input of `graphql_object`, so the optional fields are switched on and off here instead.
+macro_rules! page_info_object {
+    ($($extra_fields:tt)*) => {
+        #[graphql_object(name = "PageInfo", description = "Pagination information")]
+        impl PageInfo {
+            #[graphql(description = "Indicates whether there is a page following this current one")]
+            fn has_next_page(&self) -> bool {
+                self.has_next_page
+            }
+
+            #[graphql(description = "Indicates whether there is a page preceding this one")]
+            fn has_previous_page(&self) -> bool {
+                self.has_prev_page
+            }
+
+            #[graphql(
+                description = "An opaque cursor that when passed to after: in a query will return the previous page of results."
+            )]
+            fn start_cursor(&self) -> Option<&str> {
+                self.start_cursor.as_deref()
+            }
+
+            #[graphql(
+                description = "An opaque cursor that when passed to after: in a query will return the following page of results."
+            )]
+            fn end_cursor(&self) -> Option<&str> {
+                self.end_cursor.as_deref()
+            }
+
+            $($extra_fields)*
+        }
+    };
+}
+
+#[cfg(not(feature = "has-prev-page-alias"))]
+page_info_object!();
+
+#[cfg(feature = "has-prev-page-alias")]
+page_info_object! {
     #[graphql(
-        description = "An opaque cursor that when passed to after: in a query will return the following page of results."
+        name = "hasPrevPage",
+        description = "Indicates whether there is a page preceding this one",
+        deprecated = "Use `hasPreviousPage`."
     )]
-    pub end_cursor: Option<String>,
+    fn has_prev_page(&self) -> bool {
+        self.has_prev_page
+    }
 }
 
 /// Represents a common Relay pagination request pattern. You'd usually build this from the arguments
@@ -45,8 +88,19 @@ pub struct PageInfo {
 ///  }
 /// ```
 ///
-/// This struct can be used to represent the first and after arguments. It is also a GraphQLObject itself,
-/// which means it can be used in the schema directly.
+/// Or, when paginating backwards through the results:
+///
+/// ```graphql
+///  query {
+///      hairstyles(last: 10, before: "b2Zmc2V0OjIwOjEw") {
+///          name
+///          available_colors
+///     }
+///  }
+/// ```
+///
+/// This struct can be used to represent the first, after, last and before arguments. It is also a
+/// GraphQLObject itself, which means it can be used in the schema directly.
 ///
 #[derive(Debug, GraphQLObject, Eq, PartialEq, Clone)]
 #[graphql(description = "Page request")]
@@ -58,6 +112,16 @@ pub struct PageRequest {
     /// A cursor to use as the pointer to the start of the page.
     #[graphql(description = "A cursor to use as the pointer to the start of the page.")]
     pub after: Option<String>,
+
+    /// The number of items to return, counting back from the end of the page.
+    #[graphql(
+        description = "The number of items to return, counting back from the end of the page."
+    )]
+    pub last: Option<i32>,
+
+    /// A cursor to use as the pointer to the end of the page.
+    #[graphql(description = "A cursor to use as the pointer to the end of the page.")]
+    pub before: Option<String>,
 }
 
 impl PageRequest {
@@ -66,6 +130,35 @@ impl PageRequest {
         PageRequest {
             first,
             after: after.map(|after| after.to_encoded_string()),
+            last: None,
+            before: None,
+        }
+    }
+
+    /// Helper method to build a backwards page request from the `last` and `before` arguments of a
+    /// query resolver.
+    pub fn new_backward(last: Option<i32>, before: Option<impl Cursor>) -> Self {
+        PageRequest {
+            first: None,
+            after: None,
+            last,
+            before: before.map(|before| before.to_encoded_string()),
+        }
+    }
+
+    /// Helper method to build from all four of the Relay pagination arguments. Useful when a query
+    /// resolver supports paginating in both directions.
+    pub fn from_arguments(
+        first: Option<i32>,
+        after: Option<impl Cursor>,
+        last: Option<i32>,
+        before: Option<impl Cursor>,
+    ) -> Self {
+        PageRequest {
+            first,
+            after: after.map(|after| after.to_encoded_string()),
+            last,
+            before: before.map(|before| before.to_encoded_string()),
         }
     }
 
@@ -76,11 +169,26 @@ impl PageRequest {
     where
         T: Cursor<CursorType = T>,
     {
-        if self.after.is_none() {
-            return Ok(None);
-        }
-        let decoded_cursor = cursor_from_encoded_string(self.after.as_ref().unwrap())?;
-        Ok(Some(decoded_cursor))
+        parse_optional_cursor(self.after.as_deref())
+    }
+
+    /// Parses the `before` portion of the PageRequest into the appropriate cursor type.
+    /// Behaves the same as `parsed_cursor`.
+    pub fn parsed_before_cursor<T>(&self) -> Result<Option<T>, CursorError>
+    where
+        T: Cursor<CursorType = T>,
+    {
+        parse_optional_cursor(self.before.as_deref())
+    }
+}
+
+fn parse_optional_cursor<T>(input: Option<&str>) -> Result<Option<T>, CursorError>
+where
+    T: Cursor<CursorType = T>,
+{
+    match input {
+        Some(encoded) => Ok(Some(cursor_from_encoded_string(encoded)?)),
+        None => Ok(None),
     }
 }
 
@@ -99,6 +207,37 @@ mod tests {
             pr.after,
             Some("c3RyaW5nOnNvbWUtc3RyaW5nLWN1cnNvcg==".to_string())
         );
+        assert_eq!(pr.last, None);
+        assert_eq!(pr.before, None);
+    }
+
+    #[test]
+    fn test_new_backward() {
+        let pr = PageRequest::new_backward(
+            Some(20),
+            Some(StringCursor::new("some-string-cursor".to_string())),
+        );
+        assert_eq!(pr.first, None);
+        assert_eq!(pr.after, None);
+        assert_eq!(pr.last, Some(20));
+        assert_eq!(
+            pr.before,
+            Some("c3RyaW5nOnNvbWUtc3RyaW5nLWN1cnNvcg==".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_arguments() {
+        let pr = PageRequest::from_arguments(
+            Some(5),
+            Some(OffsetCursor::new(1, Some(10))),
+            Some(2),
+            Some(OffsetCursor::new(20, Some(10))),
+        );
+        assert_eq!(pr.first, Some(5));
+        assert_eq!(pr.after, Some("b2Zmc2V0OjE6MTA=".to_string()));
+        assert_eq!(pr.last, Some(2));
+        assert_eq!(pr.before, Some("b2Zmc2V0OjIwOjEw".to_string()));
     }
 
     #[test]
@@ -106,8 +245,113 @@ mod tests {
         let request = PageRequest {
             first: Some(10),
             after: Some("b2Zmc2V0OjE6MTA=".to_string()),
+            last: None,
+            before: None,
         };
         let decoded_cursor = request.parsed_cursor::<OffsetCursor>().unwrap();
         assert_eq!(decoded_cursor.unwrap().offset, 1);
+        assert!(
+            request
+                .parsed_before_cursor::<OffsetCursor>()
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_decoding_before_cursor_from_page_request() {
+        let request = PageRequest {
+            first: None,
+            after: None,
+            last: Some(10),
+            before: Some("b2Zmc2V0OjIwOjEw".to_string()),
+        };
+        let decoded_cursor = request.parsed_before_cursor::<OffsetCursor>().unwrap();
+        assert_eq!(decoded_cursor.unwrap().offset, 20);
+        assert!(request.parsed_cursor::<OffsetCursor>().unwrap().is_none());
+    }
+
+    mod page_info {
+        use crate::PageInfo;
+        use juniper::{
+            EmptyMutation, EmptySubscription, RootNode, Variables, graphql_object, graphql_value,
+        };
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn page_info() -> PageInfo {
+                PageInfo {
+                    has_next_page: false,
+                    has_prev_page: true,
+                    start_cursor: Some("start".to_string()),
+                    end_cursor: None,
+                }
+            }
+        }
+
+        fn execute(query: &str) -> juniper::Value {
+            let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::new());
+            let (value, errors) =
+                juniper::execute_sync(query, None, &schema, &Variables::new(), &()).unwrap();
+            assert!(errors.is_empty(), "{errors:?}");
+            value
+        }
+
+        #[test]
+        fn test_spec_field_names() {
+            assert_eq!(
+                execute("{ pageInfo { hasNextPage hasPreviousPage startCursor endCursor } }"),
+                graphql_value!({
+                    "pageInfo": {
+                        "hasNextPage": false,
+                        "hasPreviousPage": true,
+                        "startCursor": "start",
+                        "endCursor": null,
+                    }
+                })
+            );
+        }
+
+        #[cfg(not(feature = "has-prev-page-alias"))]
+        #[test]
+        fn test_no_alias_by_default() {
+            let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::new());
+            let result = juniper::execute_sync(
+                "{ pageInfo { hasPrevPage } }",
+                None,
+                &schema,
+                &Variables::new(),
+                &(),
+            );
+            assert!(result.is_err());
+        }
+
+        #[cfg(feature = "has-prev-page-alias")]
+        #[test]
+        fn test_deprecated_alias() {
+            assert_eq!(
+                execute("{ pageInfo { hasPrevPage hasPreviousPage } }"),
+                graphql_value!({
+                    "pageInfo": { "hasPrevPage": true, "hasPreviousPage": true }
+                })
+            );
+
+            let introspected = execute(
+                r#"{ __type(name: "PageInfo") { fields(includeDeprecated: true) { name deprecationReason } } }"#,
+            );
+            let fields = introspected
+                .as_object_value()
+                .and_then(|o| o.get_field_value("__type"))
+                .and_then(|t| t.as_object_value())
+                .and_then(|t| t.get_field_value("fields"))
+                .and_then(|f| f.as_list_value())
+                .unwrap();
+            assert!(fields.contains(&graphql_value!({
+                "name": "hasPrevPage",
+                "deprecationReason": "Use `hasPreviousPage`.",
+            })));
+        }
     }
 }
@@ -0,0 +1,9 @@
+//! Checks that the derive macros point at the problem when they're given input they can't handle,
+//! rather than generating nothing and leaving a confusing error elsewhere, and that the generated
+//! types only allow what they can represent.
+
+#[test]
+fn derive_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,39 @@
+//! Installing a `CursorSigner` or `CursorCipher` changes how every cursor in the process is encoded,
+//! so these checks live in their own test binary, away from the unit tests.
+
+use juniper_relay_helpers::{
+    Cursor, CursorCipher, CursorEncryptionKey, CursorSigner, CursorSigningKey, OffsetCursor,
+    StringCursor, set_cursor_cipher, set_cursor_signer,
+};
+
+#[test]
+fn installed_signer_and_cipher_protect_every_cursor() {
+    let plain = StringCursor::new("internal-token".to_string()).to_encoded_string();
+
+    set_cursor_cipher(CursorCipher::new(CursorEncryptionKey::new("enc", [9; 32]))).unwrap();
+    set_cursor_signer(CursorSigner::new(CursorSigningKey::new("sig", "secret"))).unwrap();
+
+    // Only one of each can be installed:
+    assert!(set_cursor_signer(CursorSigner::new(CursorSigningKey::new("x", "y"))).is_err());
+
+    // Round trips through the normal encode / decode path, and the GraphQLScalar path:
+    let cursor = StringCursor::new("internal-token".to_string());
+    let encoded = cursor.to_encoded_string();
+    assert_ne!(encoded, plain);
+    assert_eq!(
+        StringCursor::from_encoded_string(&encoded).unwrap().value,
+        "internal-token"
+    );
+    assert_eq!(
+        OffsetCursor::from_input(&OffsetCursor::new(4, Some(2)).to_output())
+            .unwrap()
+            .offset,
+        4
+    );
+
+    // Plain cursors from clients are rejected:
+    assert_eq!(
+        StringCursor::from_input(&plain).err().as_deref(),
+        Some("Invalid cursor signature")
+    );
+}
@@ -0,0 +1,9 @@
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+#[relay(instance(type = Versioned<String, i32>, name = "VersionedString"))]
+struct Versioned<T> {
+    value: T,
+}
+
+fn main() {}
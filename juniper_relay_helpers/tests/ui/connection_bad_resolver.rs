@@ -0,0 +1,10 @@
+use juniper::GraphQLObject;
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+#[relay(connection_resolvers(visible_count: i32))]
+struct User {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use juniper::GraphQLObject;
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, GraphQLObject, Clone, Eq, PartialEq)]
+struct Character {
+    name: String,
+}
+
+#[derive(Debug, RelayConnection, Clone, Eq, PartialEq)]
+#[relay(node = Character)]
+struct PartyMembership(String);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+struct Versioned<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+struct Borrowed<'a> {
+    name: &'a str,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(RelayConnection)]
+union Numbers {
+    int: i32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use juniper::GraphQLObject;
+use juniper_relay_helpers::RelayConnection;
+
+#[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+#[relay(connection = "Characters")]
+struct Character {
+    name: String,
+}
+
+fn main() {}
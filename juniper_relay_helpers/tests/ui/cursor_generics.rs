@@ -0,0 +1,9 @@
+use juniper_relay_helpers::RelayCursor;
+
+#[derive(Debug, RelayCursor)]
+#[cursor(tag = "post")]
+struct PostCursor<T> {
+    id: T,
+}
+
+fn main() {}
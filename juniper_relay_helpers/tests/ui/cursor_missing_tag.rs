@@ -0,0 +1,8 @@
+use juniper_relay_helpers::RelayCursor;
+
+#[derive(Debug, RelayCursor)]
+struct PostCursor {
+    id: i32,
+}
+
+fn main() {}
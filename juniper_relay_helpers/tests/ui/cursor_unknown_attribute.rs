@@ -0,0 +1,9 @@
+use juniper_relay_helpers::RelayCursor;
+
+#[derive(Debug, RelayCursor)]
+#[cursor(tag = "post", prefix = "p")]
+struct PostCursor {
+    id: i32,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use juniper_relay_helpers::IdentifierTypeDiscriminator;
+
+#[derive(IdentifierTypeDiscriminator)]
+enum EntityType {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use juniper_relay_helpers::IdentifierTypeDiscriminator;
+
+#[derive(IdentifierTypeDiscriminator)]
+struct EntityType {
+    name: String,
+}
+
+fn main() {}
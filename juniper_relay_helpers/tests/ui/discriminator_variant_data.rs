@@ -0,0 +1,9 @@
+use juniper_relay_helpers::IdentifierTypeDiscriminator;
+
+#[derive(IdentifierTypeDiscriminator)]
+enum EntityType {
+    Character,
+    Weapon(String),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use juniper_relay_helpers::RelayNodeName;
+
+#[derive(RelayNodeName)]
+struct Versioned<T> {
+    value: T,
+}
+
+fn main() {}
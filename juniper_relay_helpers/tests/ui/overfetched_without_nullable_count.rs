@@ -0,0 +1,17 @@
+use juniper::GraphQLObject;
+use juniper_relay_helpers::{
+    NodeCursorProvider, NullableCountConnection, PageRequest, RelayConnection, StringCursor,
+};
+
+#[derive(Debug, GraphQLObject, RelayConnection, Clone, Eq, PartialEq)]
+struct Character {
+    name: String,
+}
+
+fn main() {
+    let _ = CharacterRelayConnection::new_overfetched(
+        &[],
+        NodeCursorProvider::new(|character: &Character| StringCursor::new(character.name.clone())),
+        PageRequest::new(Some(2), None::<StringCursor>),
+    );
+}
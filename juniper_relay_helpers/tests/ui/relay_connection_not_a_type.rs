@@ -0,0 +1,8 @@
+use juniper_relay_helpers::relay_connection;
+
+relay_connection! {
+    #[relay(nullability = "strict")]
+    "CharacterValue"
+}
+
+fn main() {}
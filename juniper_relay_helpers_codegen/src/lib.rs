@@ -1,102 +1,853 @@
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span};
-use quote::quote;
-use syn::{Data, DeriveInput, parse_macro_input};
+use proc_macro2::Ident;
+use quote::{ToTokens, format_ident, quote};
+use syn::{Data, DeriveInput, Fields, LitInt, LitStr, Path, parse_macro_input};
 
 /// Macro that will generate Connection and Edge structs for you to use when returning lists.
-#[proc_macro_derive(RelayConnection)]
+///
+/// Can be derived for structs and for enums, such as a `GraphQLUnion` of search results. For
+/// interfaces, use `relay_connection!` instead.
+///
+/// The nullability of the generated fields can be picked with `#[relay(nullability = "...")]`:
+///
+/// - `strict`: follows the Relay spec to the letter, with a non-null `cursor: String!` on the edges.
+///   The edge's `cursor` is still an `Option<String>` in Rust, and resolving it fails if it's missing,
+///   so build edges from string cursors with `RelayEdge::try_new_raw_cursor`.
+/// - `resilient`: `edges: [Edge]` and `node: Node`, both nullable, so a single failing node doesn't
+///   null out the whole connection.
+///
+/// Without the attribute, edges and nodes are non-null and the `cursor` is nullable.
+///
+/// The generated types are named `{Node}RelayConnection` and `{Node}RelayEdge` in Rust, and
+/// `{Node}Connection` and `{Node}Edge` in the schema, where `{Node}` in the schema is the node's
+/// `#[graphql(name = "...")]` if it has one. These can be overridden with:
+///
+/// ```nocompile
+/// #[derive(GraphQLObject, RelayConnection)]
+/// #[relay(
+///     connection_type = WeaponPage,
+///     edge_type = WeaponPageEdge,
+///     connection_name = "WeaponPage",
+///     edge_name = "WeaponPageEdge",
+///     connection_description = "A page of weapons.",
+///     edge_description = "A weapon in a page."
+/// )]
+/// struct Weapon { ... }
+/// ```
+///
+/// For edges that carry data of their own, derive it on a struct holding that data instead, with the
+/// node type given by `#[relay(node = ...)]`. The fields of the struct are added to the generated
+/// edge, and the connection is built from `(node, edge data)` pairs:
+///
+/// ```nocompile
+/// #[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+/// #[relay(node = Character)]
+/// struct PartyMembership {
+///     /// When the character joined the party.
+///     joined_at: String,
+/// }
+/// ```
+///
+/// Extra fields can be added to the connection with `connection_fields`. They start out as their
+/// `Default` when the connection is built, ready to be filled in:
+///
+/// ```nocompile
+/// #[derive(GraphQLObject, RelayConnection)]
+/// #[relay(connection_fields(
+///     /// The combined value of every order, not just this page.
+///     total_amount: i32,
+/// ))]
+/// struct Order { ... }
+/// ```
+///
+/// Generic nodes get generic Connection and Edge types. A generic type can't have a single GraphQL
+/// name, so each instantiation used in the schema is named with `instance`:
+///
+/// ```nocompile
+/// #[derive(Clone, RelayConnection)]
+/// #[relay(instance(type = Versioned<Character>, name = "VersionedCharacter"))]
+/// struct Versioned<T> { ... }
+/// ```
+///
+/// This gives `VersionedRelayConnection<Character>` the GraphQL name `VersionedCharacterConnection`.
+///
+/// The `count` of the connection is a non-null `Int!`. `#[relay(nullable_count)]` makes it a nullable
+/// `count: Int` instead, for connections built without a total count, and implements
+/// `NullableCountConnection` so that they can be built with `NullableCountConnection::new_overfetched`.
+///
+/// `#[relay(nodes_field)]` adds a `nodes` field to the connection alongside `edges`, resolved from
+/// the nodes of the edges.
+///
+/// Resolvers can be added to the connection and edge with `connection_resolvers` and
+/// `edge_resolvers`, each naming the function that resolves it. With `context = ...`, the generated
+/// types use that juniper context and the functions are passed it after the connection or edge:
+///
+/// ```nocompile
+/// #[derive(GraphQLObject, RelayConnection)]
+/// #[relay(
+///     context = Database,
+///     connection_resolvers(visible_count: i32 = count_visible_characters),
+///     edge_resolvers(async last_seen: Option<String> = load_last_seen),
+/// )]
+/// struct Character { ... }
+///
+/// fn count_visible_characters(connection: &CharacterRelayConnection, db: &Database) -> i32 { ... }
+/// async fn load_last_seen(edge: &CharacterRelayEdge, db: &Database) -> Option<String> { ... }
+/// ```
+///
+/// The generated types derive `Debug`, `Clone`, `Eq` and `PartialEq`, which the node needs to
+/// implement too. Pick the derives with `#[relay(derive(...))]` for nodes that don't, such as
+/// `#[relay(derive(Debug))]` for nodes that can't be cloned.
+#[proc_macro_derive(RelayConnection, attributes(relay, graphql))]
 pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    expand_relay_connection(input, true)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let out = match input.data {
-        Data::Struct(_s) => {
-            let connection_gql_name = format!("{}Connection", input.ident);
-            let connection_gql_desc = format!("Connection type for {}.", input.ident);
-            let edge_gql_name = format!("{}Edge", input.ident);
-            let edge_gql_desc = format!("Edge type for {}.", input.ident);
-            let connection_name = Ident::new(
-                &format!("{}RelayConnection", input.ident),
-                Span::mixed_site(),
-            );
-            let edge_name = Ident::new(&format!("{}RelayEdge", input.ident), Span::mixed_site());
-            let edge_trait_name = Ident::new(
-                &format!("{}RelayEdgeTrait", input.ident),
-                Span::mixed_site(),
-            );
-            let struct_name = input.ident;
+/// Generates Connection and Edge structs for a node type that can't be derived on, such as the
+/// `{Name}Value` type of a `#[graphql_interface]`.
+///
+/// Takes the same `#[relay(...)]` and `#[graphql(name = "...")]` attributes as the derive, followed
+/// by the visibility of the generated types and the name of the node type:
+///
+/// ```nocompile
+/// #[graphql_interface(for = [Character, Location])]
+/// struct Searchable {
+///     name: String,
+/// }
+///
+/// relay_connection! {
+///     #[graphql(name = "Searchable")]
+///     pub SearchableValue
+/// }
+/// ```
+///
+/// As interface values don't implement `PartialEq`, the generated types only derive `Debug` and
+/// `Clone` by default.
+#[proc_macro]
+pub fn relay_connection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input with parse_relay_connection_input);
+    expand_relay_connection(input, false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-            quote! {
-                #[derive(juniper::GraphQLObject, Debug, Clone, Eq, PartialEq)]
-                #[graphql(
-                    name = #connection_gql_name,
-                    description = #connection_gql_desc
-                )]
-                pub struct #connection_name {
-                    pub count: i32,
-                    pub edges: Vec<#edge_name>,
-                    pub page_info: juniper_relay_helpers::PageInfo,
-                }
-
-                use juniper_relay_helpers::RelayEdge as #edge_trait_name;
-                impl juniper_relay_helpers::RelayConnection for #connection_name {
-                    type EdgeType = #edge_name;
-                    type NodeType = #struct_name;
-
-                    fn new(
-                        nodes: &[#struct_name],
-                        total_items: i32,
-                        cursor_provider: impl juniper_relay_helpers::CursorProvider,
-                        page_request: Option<juniper_relay_helpers::PageRequest>
-                    ) -> Self {
-                        let metadata = juniper_relay_helpers::PaginationMetadata {
-                            total_count: total_items,
-                            page_request
-                        };
-                        Self {
-                            count: total_items,
-                            edges: nodes.iter().enumerate().map(|(idx, node)| {
-                                #edge_name::new(
-                                    node.clone(),
-                                    cursor_provider.get_cursor_for_item(&metadata, idx as i32, node)
-                                )
-                            }).collect(),
-                            page_info: cursor_provider.get_page_info(&metadata, &nodes),
-                        }
+/// Parses the input of `relay_connection!` into the same shape as a derive input, so that both go
+/// through `expand_relay_connection`.
+fn parse_relay_connection_input(input: syn::parse::ParseStream) -> syn::Result<DeriveInput> {
+    let attrs = input.call(syn::Attribute::parse_outer)?;
+    let vis: syn::Visibility = input.parse()?;
+    let ident: Ident = input.parse()?;
+    input.parse::<Option<syn::Token![,]>>()?;
+    Ok(DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics: syn::Generics::default(),
+        data: Data::Enum(syn::DataEnum {
+            enum_token: Default::default(),
+            brace_token: Default::default(),
+            variants: Default::default(),
+        }),
+    })
+}
+
+/// The shape of the fields of the generated Connection and Edge types.
+#[derive(Clone, Copy)]
+enum Nullability {
+    Default,
+    Strict,
+    Resilient,
+}
+
+/// The `name` from the `#[graphql(...)]` attributes of the node, if it has been renamed.
+fn graphql_name(input: &DeriveInput) -> syn::Result<Option<String>> {
+    let mut name = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("graphql")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                meta.input.parse::<proc_macro2::TokenTree>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(name)
+}
+
+fn expand_relay_connection(
+    input: DeriveInput,
+    comparable: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if let Data::Union(_) = &input.data {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "RelayConnection can only be derived for structs and enums",
+        ));
+    }
+    if let Some(param) = input
+        .generics
+        .params
+        .iter()
+        .find(|p| !matches!(p, syn::GenericParam::Type(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            "RelayConnection only supports generic type parameters",
+        ));
+    }
+
+    let node_gql_name = graphql_name(&input)?.unwrap_or_else(|| input.ident.to_string());
+    let mut nullability = Nullability::Default;
+    let mut connection_name = format_ident!("{}RelayConnection", input.ident);
+    let mut edge_name = format_ident!("{}RelayEdge", input.ident);
+    let mut connection_gql_name = format!("{node_gql_name}Connection");
+    let mut edge_gql_name = format!("{node_gql_name}Edge");
+    let mut connection_gql_desc = format!("Connection type for {node_gql_name}.");
+    let mut edge_gql_desc = format!("Edge type for {node_gql_name}.");
+    let mut edge_node: Option<syn::Type> = None;
+    let mut connection_fields: Vec<syn::Field> = vec![];
+    let mut instances: Vec<(syn::Type, LitStr)> = vec![];
+    let mut derives: Option<Vec<Path>> = None;
+    let mut nodes_field = false;
+    let mut nullable_count = false;
+    let mut context: Option<syn::Type> = None;
+    let mut connection_resolvers: Vec<Resolver> = vec![];
+    let mut edge_resolvers: Vec<Resolver> = vec![];
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("relay")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nullability") {
+                let value: LitStr = meta.value()?.parse()?;
+                nullability = match value.value().as_str() {
+                    "strict" => Nullability::Strict,
+                    "resilient" => Nullability::Resilient,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            "nullability must be `strict` or `resilient`",
+                        ));
+                    }
+                };
+            } else if meta.path.is_ident("node") {
+                edge_node = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("connection_fields") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                connection_fields.extend(
+                    content.parse_terminated(syn::Field::parse_named, syn::Token![,])?,
+                );
+            } else if meta.path.is_ident("instance") {
+                let mut instance_type: Option<syn::Type> = None;
+                let mut instance_name: Option<LitStr> = None;
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("type") {
+                        instance_type = Some(nested.value()?.parse()?);
+                        Ok(())
+                    } else if nested.path.is_ident("name") {
+                        instance_name = Some(nested.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(nested.error("unsupported instance attribute, expected `type` or `name`"))
+                    }
+                })?;
+                match (instance_type, instance_name) {
+                    (Some(instance_type), Some(instance_name)) => {
+                        instances.push((instance_type, instance_name));
+                    }
+                    _ => {
+                        return Err(meta.error(
+                            "instance requires both `type = ...` and `name = \"...\"`",
+                        ));
                     }
                 }
+            } else if meta.path.is_ident("nodes_field") {
+                nodes_field = true;
+            } else if meta.path.is_ident("nullable_count") {
+                nullable_count = true;
+            } else if meta.path.is_ident("context") {
+                context = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("connection_resolvers") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                connection_resolvers
+                    .extend(content.parse_terminated(Resolver::parse, syn::Token![,])?);
+            } else if meta.path.is_ident("edge_resolvers") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                edge_resolvers.extend(content.parse_terminated(Resolver::parse, syn::Token![,])?);
+            } else if meta.path.is_ident("derive") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                derives = Some(
+                    content
+                        .parse_terminated(Path::parse_mod_style, syn::Token![,])?
+                        .into_iter()
+                        .collect(),
+                );
+            } else if meta.path.is_ident("connection_type") {
+                connection_name = meta.value()?.parse()?;
+            } else if meta.path.is_ident("edge_type") {
+                edge_name = meta.value()?.parse()?;
+            } else if meta.path.is_ident("connection_name") {
+                connection_gql_name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("edge_name") {
+                edge_gql_name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("connection_description") {
+                connection_gql_desc = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("edge_description") {
+                edge_gql_desc = meta.value()?.parse::<LitStr>()?.value();
+            } else {
+                return Err(meta.error(
+                    "unsupported relay attribute, expected `nullability`, `node`, `connection_fields`, \
+                     `instance`, `derive`, `nodes_field`, `nullable_count`, `context`, `connection_resolvers`, \
+                     `edge_resolvers`, `connection_type`, `edge_type`, `connection_name`, `edge_name`, \
+                     `connection_description` or `edge_description`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
 
-                #[derive(juniper::GraphQLObject, Debug, Clone, Eq, PartialEq)]
-                #[graphql(
-                    name = #edge_gql_name,
-                    description = #edge_gql_desc
-                )]
-                pub struct #edge_name {
-                    pub node: #struct_name,
-                    pub cursor: Option<String>,
-                }
+    let struct_name = &input.ident;
+    let vis = &input.vis;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let type_params = generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    if let Some((instance_type, _)) = instances.first().filter(|_| type_params.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            instance_type,
+            "instance is only needed for nodes with generic parameters",
+        ));
+    }
+    if !type_params.is_empty() && instances.is_empty() {
+        return Err(syn::Error::new_spanned(
+            generics,
+            "generic nodes need an `instance(type = ..., name = \"...\")` for each instantiation used \
+             in the schema",
+        ));
+    }
+    let edge_type = quote! { #edge_name #ty_generics };
 
-                impl juniper_relay_helpers::RelayEdge for #edge_name {
-                    type NodeType = #struct_name;
-                    fn new(node: Self::NodeType, cursor: impl juniper_relay_helpers::Cursor) -> Self {
-                        Self {
-                            node: node,
-                            cursor: Some(cursor.to_encoded_string()),
-                        }
-                    }
+    // When deriving on edge data, the node is the type given in `node = ...` and the fields of the
+    // struct are added to the edge.
+    let (node_item, edge_fields) = match &edge_node {
+        Some(edge_node) => {
+            let Data::Struct(syn::DataStruct {
+                fields: Fields::Named(fields),
+                ..
+            }) = &input.data
+            else {
+                return Err(syn::Error::new_spanned(
+                    struct_name,
+                    "edge data for RelayConnection must be a struct with named fields",
+                ));
+            };
+            (quote! { #edge_node }, graphql_fields(fields.named.iter()))
+        }
+        None => (quote! { #struct_name #ty_generics }, vec![]),
+    };
+    let node_type_param = match &edge_node {
+        Some(edge_node) => quote! { (#edge_node, #struct_name #ty_generics) },
+        None => quote! { #struct_name #ty_generics },
+    };
+    let node_binding = match &edge_node {
+        Some(_) => quote! { let (node, edge_data) = node; },
+        None => quote! {},
+    };
+    let connection_fields = graphql_fields(connection_fields.iter());
+
+    let derives = match derives {
+        Some(derives) => quote! { #(#derives),* },
+        None if comparable => quote! { Debug, Clone, Eq, PartialEq },
+        None => quote! { Debug, Clone },
+    };
+
+    let (edges_type, edges_value, edges_part) = match nullability {
+        Nullability::Resilient => (
+            quote! { Option<Vec<Option<#edge_type>>> },
+            quote! { Some(edges.into_iter().map(Some).collect()) },
+            quote! { self.edges.into_iter().flatten().flatten().collect() },
+        ),
+        _ => (
+            quote! { Vec<#edge_type> },
+            quote! { edges },
+            quote! { self.edges },
+        ),
+    };
+    let nullable_count_impl = nullable_count.then(|| {
+        quote! {
+            impl #impl_generics juniper_relay_helpers::NullableCountConnection for #connection_name #ty_generics #where_clause {}
+        }
+    });
+    let (count_type, count_value, count_part) = if nullable_count {
+        (
+            quote! { Option<i32> },
+            quote! { total_items },
+            quote! { self.count },
+        )
+    } else {
+        (
+            quote! { i32 },
+            quote! { total_items.unwrap_or_default() },
+            quote! { Some(self.count) },
+        )
+    };
+    let (node_type, node_value) = match nullability {
+        Nullability::Resilient => (quote! { Option<#node_item> }, quote! { Some(node) }),
+        _ => (quote! { #node_item }, quote! { node }),
+    };
+    // Edges with `strict` nullability keep an optional cursor too, so that they can be built from
+    // one, but resolve it as non-null. Their connections always build the cursors.
+    let strict_cursor = matches!(nullability, Nullability::Strict);
+    let requires_cursor = strict_cursor.then(|| quote! { const REQUIRES_CURSOR: bool = true; });
+    let cursor_resolver = if strict_cursor {
+        quote! {
+            fn cursor(&self) -> Result<&str, String> {
+                self.cursor
+                    .as_deref()
+                    .ok_or_else(|| juniper_relay_helpers::CursorError::MissingCursor.to_string())
+            }
+        }
+    } else {
+        quote! {
+            fn cursor(&self) -> &Option<String> {
+                &self.cursor
+            }
+        }
+    };
+
+    // Without generics the GraphQL objects are derived, unless they need a context or resolvers of
+    // their own, such as `nodes` or the non-null `cursor` of `strict` edges.
+    let derive_connection = type_params.is_empty()
+        && !nodes_field
+        && context.is_none()
+        && connection_resolvers.is_empty();
+    let derive_edge =
+        type_params.is_empty() && !strict_cursor && context.is_none() && edge_resolvers.is_empty();
 
-                    fn new_raw_cursor(node: Self::NodeType, cursor: Option<String>) -> Self {
-                        Self {
-                            node: node,
-                            cursor: cursor,
+    let connection_field_definitions = connection_fields
+        .iter()
+        .map(|f| f.definition(derive_connection));
+    let connection_field_names = connection_fields.iter().map(|f| &f.name);
+    let edge_field_definitions = edge_fields.iter().map(|f| f.definition(derive_edge));
+    let edge_data = edge_fields
+        .iter()
+        .map(|f| {
+            let name = &f.name;
+            quote! { #name: edge_data.#name, }
+        })
+        .collect::<Vec<_>>();
+    let edge_field_names = edge_fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+
+    // Puts the node and edge data back together when taking an edge apart.
+    let node_item_part = match &edge_node {
+        Some(_) => quote! { (node, #struct_name { #(#edge_field_names),* }) },
+        None => quote! { node },
+    };
+    let node_part = match nullability {
+        Nullability::Resilient if edge_node.is_some() => {
+            quote! { node.map(|node| #node_item_part) }
+        }
+        Nullability::Resilient => quote! { node },
+        _ => quote! { Some(#node_item_part) },
+    };
+
+    // Builds the `graphql_object` impls, for when the GraphQL objects can't be derived. `concrete`
+    // turns the types of the fields into those of the instance being built for.
+    let resolvers = |fields: &[GraphQLField], concrete: Concrete| {
+        fields
+            .iter()
+            .map(|f| {
+                let (attrs, name) = (&f.attrs, &f.name);
+                let ty = concrete(&f.ty);
+                quote! { #(#attrs)* fn #name(&self) -> &#ty { &self.#name } }
+            })
+            .collect::<Vec<_>>()
+    };
+    // The resolvers given in `connection_resolvers` and `edge_resolvers`, which call the function
+    // they name with the object, and the context if there is one.
+    let user_resolvers = |user_resolvers: &[Resolver], concrete: Concrete| {
+        user_resolvers
+            .iter()
+            .map(|r| {
+                let (attrs, asyncness, name) = (&r.attrs, &r.asyncness, &r.name);
+                let ty = concrete(&r.ty.to_token_stream());
+                let with = concrete(&r.with.to_token_stream());
+                let awaited = asyncness.map(|_| quote! { .await });
+                match &context {
+                    Some(context) => {
+                        let context = concrete(&context.to_token_stream());
+                        quote! {
+                            #(#attrs)*
+                            #asyncness fn #name(&self, context: &#context) -> #ty {
+                                #with(self, context)#awaited
+                            }
                         }
                     }
+                    None => quote! {
+                        #(#attrs)*
+                        #asyncness fn #name(&self) -> #ty {
+                            #with(self)#awaited
+                        }
+                    },
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let object_arguments = |concrete: Concrete, gql_name: &str, gql_desc: &str| {
+        let context = context.as_ref().map(|context| {
+            let context = concrete(&context.to_token_stream());
+            quote! { , context = #context }
+        });
+        quote! { name = #gql_name, description = #gql_desc #context }
+    };
+    let connection_object_impl = |concrete: Concrete, gql_name: &str, gql_desc: &str| {
+        let concrete_generics = concrete(&quote! { #ty_generics });
+        let object_arguments = object_arguments(concrete, gql_name, gql_desc);
+        let field_resolvers = resolvers(&connection_fields, concrete);
+        let user_resolvers = user_resolvers(&connection_resolvers, concrete);
+        let (edges_type, node_item) = (concrete(&edges_type), concrete(&node_item));
+        let nodes_resolver = nodes_field.then(|| {
+            let (nodes_type, nodes_value) = match nullability {
+                Nullability::Resilient => (
+                    quote! { Option<Vec<Option<&#node_item>>> },
+                    quote! {
+                        self.edges.as_ref().map(|edges| {
+                            edges
+                                .iter()
+                                .map(|edge| edge.as_ref().and_then(|edge| edge.node.as_ref()))
+                                .collect()
+                        })
+                    },
+                ),
+                _ => (
+                    quote! { Vec<&#node_item> },
+                    quote! { self.edges.iter().map(|edge| &edge.node).collect() },
+                ),
+            };
+            quote! {
+                #[graphql(description = "The nodes of the edges, for when their cursors aren't needed.")]
+                fn nodes(&self) -> #nodes_type {
+                    #nodes_value
+                }
+            }
+        });
+        quote! {
+            #[juniper::graphql_object(#object_arguments)]
+            impl #connection_name #concrete_generics {
+                fn count(&self) -> #count_type {
+                    self.count
+                }
+
+                fn edges(&self) -> &#edges_type {
+                    &self.edges
+                }
+
+                #nodes_resolver
+
+                fn page_info(&self) -> &juniper_relay_helpers::PageInfo {
+                    &self.page_info
+                }
+
+                #(#field_resolvers)*
+                #(#user_resolvers)*
+            }
+        }
+    };
+    let edge_object_impl = |concrete: Concrete, gql_name: &str, gql_desc: &str| {
+        let concrete_generics = concrete(&quote! { #ty_generics });
+        let object_arguments = object_arguments(concrete, gql_name, gql_desc);
+        let field_resolvers = resolvers(&edge_fields, concrete);
+        let user_resolvers = user_resolvers(&edge_resolvers, concrete);
+        let node_type = concrete(&node_type);
+        quote! {
+            #[juniper::graphql_object(#object_arguments)]
+            impl #edge_name #concrete_generics {
+                fn node(&self) -> &#node_type {
+                    &self.node
+                }
+
+                #cursor_resolver
+
+                #(#field_resolvers)*
+                #(#user_resolvers)*
+            }
+        }
+    };
+
+    // Objects that aren't derived get `graphql_object` impls instead. Generic types can't be given a
+    // single GraphQL name, so each of their named instances gets its own impls.
+    let (connection_object, edge_object, graphql_objects) = if type_params.is_empty() {
+        let same = |tokens: &proc_macro2::TokenStream| tokens.clone();
+        let mut graphql_objects = vec![];
+        let connection_object = if derive_connection {
+            quote! {
+                #[derive(juniper::GraphQLObject)]
+                #[graphql(name = #connection_gql_name, description = #connection_gql_desc)]
+            }
+        } else {
+            graphql_objects.push(connection_object_impl(
+                &same,
+                &connection_gql_name,
+                &connection_gql_desc,
+            ));
+            quote! {}
+        };
+        let edge_object = if derive_edge {
+            quote! {
+                #[derive(juniper::GraphQLObject)]
+                #[graphql(name = #edge_gql_name, description = #edge_gql_desc)]
+            }
+        } else {
+            graphql_objects.push(edge_object_impl(&same, &edge_gql_name, &edge_gql_desc));
+            quote! {}
+        };
+        (connection_object, edge_object, graphql_objects)
+    } else {
+        let objects = instances
+            .iter()
+            .map(|(instance_type, instance_name)| {
+                let args = instance_arguments(instance_type, struct_name, type_params.len())?;
+                let substitutions = type_params.iter().zip(args).collect::<Vec<_>>();
+                let concrete = |tokens: &proc_macro2::TokenStream| {
+                    substitute_type_params(tokens.clone(), &substitutions)
+                };
+                let name = instance_name.value();
+                let connection_impl = connection_object_impl(
+                    &concrete,
+                    &format!("{name}Connection"),
+                    &format!("Connection type for {name}."),
+                );
+                let edge_impl = edge_object_impl(
+                    &concrete,
+                    &format!("{name}Edge"),
+                    &format!("Edge type for {name}."),
+                );
+                Ok(quote! { #connection_impl #edge_impl })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        (quote! {}, quote! {}, objects)
+    };
+
+    Ok(quote! {
+        #[derive(#derives)]
+        #connection_object
+        #vis struct #connection_name #generics #where_clause {
+            pub count: #count_type,
+            pub edges: #edges_type,
+            pub page_info: juniper_relay_helpers::PageInfo,
+            #(#connection_field_definitions)*
+        }
+
+        impl #impl_generics juniper_relay_helpers::RelayConnection for #connection_name #ty_generics #where_clause {
+            type EdgeType = #edge_type;
+            type NodeType = #node_type_param;
+
+            fn from_edges(
+                edges: Vec<#edge_type>,
+                total_items: Option<i32>,
+                page_info: juniper_relay_helpers::PageInfo
+            ) -> Self {
+                Self {
+                    count: #count_value,
+                    edges: #edges_value,
+                    page_info,
+                    #(#connection_field_names: Default::default(),)*
+                }
+            }
+        }
+
+        impl #impl_generics juniper_relay_helpers::RelayConnectionParts for #connection_name #ty_generics #where_clause {
+            fn into_parts(self) -> (Vec<#edge_type>, Option<i32>, juniper_relay_helpers::PageInfo) {
+                (#edges_part, #count_part, self.page_info)
+            }
+        }
+
+        #nullable_count_impl
+
+        #[derive(#derives)]
+        #edge_object
+        #vis struct #edge_name #generics #where_clause {
+            pub node: #node_type,
+            pub cursor: Option<String>,
+            #(#edge_field_definitions)*
+        }
+
+        impl #impl_generics juniper_relay_helpers::RelayEdge for #edge_name #ty_generics #where_clause {
+            type NodeType = #node_type_param;
+            #requires_cursor
+
+            fn new(node: Self::NodeType, cursor: impl juniper_relay_helpers::Cursor) -> Self {
+                #node_binding
+                Self {
+                    node: #node_value,
+                    cursor: Some(cursor.to_encoded_string()),
+                    #(#edge_data)*
                 }
             }
+
+            fn new_raw_cursor(node: Self::NodeType, cursor: Option<String>) -> Self {
+                #node_binding
+                Self {
+                    node: #node_value,
+                    cursor,
+                    #(#edge_data)*
+                }
+            }
+        }
+
+        impl #impl_generics juniper_relay_helpers::RelayEdgeParts for #edge_name #ty_generics #where_clause {
+            fn into_parts(self) -> (Option<Self::NodeType>, Option<String>) {
+                let Self { node, cursor, #(#edge_field_names,)* } = self;
+                (#node_part, cursor)
+            }
+        }
+
+        #(#graphql_objects)*
+    })
+}
+
+/// Turns types written in terms of the node's generic parameters into those of an instance.
+type Concrete<'a> = &'a dyn Fn(&proc_macro2::TokenStream) -> proc_macro2::TokenStream;
+
+/// A field added to a generated Connection or Edge, with the attributes that are passed on to it.
+struct GraphQLField {
+    attrs: Vec<syn::Attribute>,
+    name: Option<Ident>,
+    ty: proc_macro2::TokenStream,
+}
+
+impl GraphQLField {
+    /// The field of the struct. `#[graphql(...)]` attributes are only kept when the GraphQL object
+    /// is derived, as they're given to the resolver of the field otherwise.
+    fn definition(&self, derived: bool) -> proc_macro2::TokenStream {
+        let (name, ty) = (&self.name, &self.ty);
+        let attrs = self
+            .attrs
+            .iter()
+            .filter(|a| derived || a.path().is_ident("doc"));
+        quote! { #(#attrs)* pub #name: #ty, }
+    }
+}
+
+/// A resolver added to a generated Connection or Edge by `connection_resolvers` or `edge_resolvers`,
+/// written as `[async] name: Type = path::to::function`.
+struct Resolver {
+    attrs: Vec<syn::Attribute>,
+    asyncness: Option<syn::Token![async]>,
+    name: Ident,
+    ty: syn::Type,
+    with: syn::ExprPath,
+}
+
+impl Resolver {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let asyncness = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<syn::Token![:]>()?;
+        let ty = input.parse()?;
+        if !input.peek(syn::Token![=]) {
+            return Err(input.error(
+                "resolvers need a function to call, written as `name: Type = path::to::function`",
+            ));
         }
-        _ => quote! {},
+        input.parse::<syn::Token![=]>()?;
+        let with = input.parse()?;
+        Ok(Resolver {
+            attrs,
+            asyncness,
+            name,
+            ty,
+            with,
+        })
+    }
+}
+
+/// Collects the fields, keeping only their doc comments and `#[graphql(...)]` attributes.
+fn graphql_fields<'a>(fields: impl Iterator<Item = &'a syn::Field>) -> Vec<GraphQLField> {
+    fields
+        .map(|f| GraphQLField {
+            attrs: f
+                .attrs
+                .iter()
+                .filter(|a| a.path().is_ident("doc") || a.path().is_ident("graphql"))
+                .cloned()
+                .collect(),
+            name: f.ident.clone(),
+            ty: f.ty.to_token_stream(),
+        })
+        .collect()
+}
+
+/// The type arguments of an `instance(type = ...)`, which must be the node with a type for each
+/// generic parameter.
+fn instance_arguments(
+    instance_type: &syn::Type,
+    struct_name: &Ident,
+    count: usize,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let error = || {
+        syn::Error::new_spanned(
+            instance_type,
+            format!(
+                "instance type must be `{struct_name}<...>` with a type for each generic parameter"
+            ),
+        )
+    };
+    let syn::Type::Path(path) = instance_type else {
+        return Err(error());
+    };
+    let segment = path.path.segments.last().ok_or_else(error)?;
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Err(error());
     };
+    if segment.ident != *struct_name || arguments.args.len() != count {
+        return Err(error());
+    }
+    arguments
+        .args
+        .iter()
+        .map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Ok(ty.to_token_stream()),
+            _ => Err(error()),
+        })
+        .collect()
+}
 
-    out.into()
+/// Swaps the generic type parameters in some tokens for the concrete types of an instance.
+fn substitute_type_params(
+    tokens: proc_macro2::TokenStream,
+    substitutions: &[(&Ident, proc_macro2::TokenStream)],
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => {
+                match substitutions.iter().find(|(param, _)| **param == ident) {
+                    Some((_, ty)) => ty.clone(),
+                    None => proc_macro2::TokenTree::Ident(ident).into(),
+                }
+            }
+            proc_macro2::TokenTree::Group(group) => {
+                let mut substituted = proc_macro2::Group::new(
+                    group.delimiter(),
+                    substitute_type_params(group.stream(), substitutions),
+                );
+                substituted.set_span(group.span());
+                proc_macro2::TokenTree::Group(substituted).into()
+            }
+            other => other.into(),
+        })
+        .collect()
 }
 
 /// Macro for extending an Enum with the traits required for it to be used as a type discriminator
@@ -122,46 +873,376 @@ pub fn macro_relay_connection_node(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(IdentifierTypeDiscriminator)]
 pub fn macro_type_discriminator(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    expand_type_discriminator(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let out = match input.data {
-        Data::Enum(e) => {
-            let enum_name = input.ident;
-            let enum_display_variants = e.variants.iter().map(|v| {
-                let v_string = v.ident.to_string().to_lowercase();
-                quote! {
-                    #enum_name::#v => { write!(f, #v_string) }
-                }
-            });
-            let fromstr_display_variants = e.variants.iter().map(|v| {
-                let v_string = v.ident.to_string().to_lowercase();
-                let v = v.ident.clone();
-                quote! {
-                    #v_string => Ok(#enum_name::#v)
+fn expand_type_discriminator(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    reject_generics(&input, "IdentifierTypeDiscriminator")?;
+    let Data::Enum(e) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "IdentifierTypeDiscriminator can only be derived for enums",
+        ));
+    };
+    if e.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "IdentifierTypeDiscriminator needs at least one variant",
+        ));
+    }
+    if let Some(variant) = e
+        .variants
+        .iter()
+        .find(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            &variant.fields,
+            "IdentifierTypeDiscriminator variants can't carry data",
+        ));
+    }
+
+    let enum_name = &input.ident;
+    let enum_display_variants = e.variants.iter().map(|v| {
+        let v_string = v.ident.to_string().to_lowercase();
+        quote! {
+            #enum_name::#v => { write!(f, #v_string) }
+        }
+    });
+    let fromstr_display_variants = e.variants.iter().map(|v| {
+        let v_string = v.ident.to_string().to_lowercase();
+        let v = v.ident.clone();
+        quote! {
+            #v_string => Ok(#enum_name::#v),
+        }
+    });
+
+    Ok(quote! {
+        impl std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#enum_display_variants),*
                 }
-            });
+            }
+        }
 
-            quote! {
-                impl std::fmt::Display for #enum_name {
-                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                        match self {
-                            #(#enum_display_variants),*
-                        }
-                    }
+        impl std::str::FromStr for #enum_name {
+            type Err = &'static str;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#fromstr_display_variants)*
+                    &_ => Err("Invalid type delimiter")
                 }
+            }
+        }
+    })
+}
 
-                impl std::str::FromStr for #enum_name {
-                    type Err = &'static str;
-                    fn from_str(s: &str) -> Result<Self, Self::Err> {
-                        match s {
-                            #(#fromstr_display_variants),*,
-                            &_ => Err("Invalid type delimiter")
-                        }
+/// Implements `RelayNodeName` for a node, so that it can be used in the library's generic
+/// `Connection<N>` and `Edge<N>` types from the `generic-connection` feature. They're named after the
+/// GraphQL name of the node:
+///
+/// ```nocompile
+/// #[derive(GraphQLObject, RelayNodeName)]
+/// #[graphql(name = "Hero")]
+/// pub struct PlayableCharacter { ... }
+/// ```
+///
+/// `Connection<PlayableCharacter>` is then `HeroConnection` in the schema, and its edges `HeroEdge`.
+#[proc_macro_derive(RelayNodeName, attributes(graphql))]
+pub fn macro_relay_node_name(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_relay_node_name(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_relay_node_name(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    reject_generics(&input, "RelayNodeName")?;
+    let node_name = &input.ident;
+    let gql_name = graphql_name(&input)?.unwrap_or_else(|| node_name.to_string());
+    let connection_name = format!("{gql_name}Connection");
+    let edge_name = format!("{gql_name}Edge");
+
+    Ok(quote! {
+        impl juniper_relay_helpers::RelayNodeName for #node_name {
+            const CONNECTION_NAME: &'static str = #connection_name;
+            const EDGE_NAME: &'static str = #edge_name;
+        }
+    })
+}
+
+/// Generic parameters aren't supported by the derives, so point at them rather than failing later on.
+fn reject_generics(input: &DeriveInput, derive: &str) -> syn::Result<()> {
+    if input.generics.params.is_empty() {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("{derive} can't be derived for types with generic parameters"),
+        ))
+    }
+}
+
+/// Macro that implements `Cursor` for a struct whose fields are each `FromStr + Display`, along with
+/// `Display` and a `GraphQLScalar` so it can be used directly as a resolver argument.
+///
+/// ```nocompile
+/// use juniper_relay_helpers::RelayCursor;
+///
+/// #[derive(Debug, RelayCursor)]
+/// #[cursor(tag = "message")]
+/// pub struct MessageCursor {
+///     pub sent_at: String,
+///     pub id: i32,
+/// }
+/// ```
+///
+/// The raw string of the cursor is the tag followed by each of the fields in order, separated by
+/// colons: `message:2025-04-24T10%3A30%3A00Z:12`. Field values are escaped so they can safely contain
+/// colons. The GraphQL scalar is named after the struct, or you can set it with `name = "..."`.
+///
+/// Add `default` to also implement `Default`, with each field set to its own default. The fields then
+/// need to implement `Default` too: `#[cursor(tag = "message", default)]`.
+///
+/// When the fields change, bump the cursor's `version` and point `upgrade` at a function that decodes
+/// the previous format, so that cursors issued before the change are still accepted:
+///
+/// ```nocompile
+/// #[derive(Debug, RelayCursor)]
+/// #[cursor(tag = "message", version = 2, upgrade(version = 1, with = MessageCursor::from_v1))]
+/// pub struct MessageCursor {
+///     pub channel: String,
+///     pub sent_at: String,
+///     pub id: i32,
+/// }
+///
+/// impl MessageCursor {
+///     fn from_v1(raw: &str, parts: Vec<&str>) -> Result<Self, CursorError> { ... }
+/// }
+/// ```
+///
+/// To encode the cursor with something other than the installed `CursorCodec`, point `codec` at a
+/// `static` one: `#[cursor(tag = "message", codec = MESSAGE_CODEC)]`.
+///
+#[proc_macro_derive(RelayCursor, attributes(cursor))]
+pub fn macro_relay_cursor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_relay_cursor(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_relay_cursor(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    reject_generics(&input, "RelayCursor")?;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "RelayCursor can only be derived for structs",
+        ));
+    };
+
+    let mut tag: Option<LitStr> = None;
+    let mut scalar_name = LitStr::new(&input.ident.to_string(), input.ident.span());
+    let mut version: Option<LitInt> = None;
+    let mut upgraders: Vec<(LitInt, Path)> = vec![];
+    let mut codec: Option<Path> = None;
+    let mut default = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("cursor")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().is_empty()
+                    || value.value().contains(':')
+                    || value.value().starts_with('@')
+                {
+                    return Err(meta.error(
+                        "cursor tag must be non-empty, not contain `:` and not start with `@`",
+                    ));
+                }
+                tag = Some(value);
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                scalar_name = meta.value()?.parse()?;
+                Ok(())
+            } else if meta.path.is_ident("version") {
+                version = Some(parse_cursor_version(meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("codec") {
+                codec = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else if meta.path.is_ident("upgrade") {
+                let mut from_version: Option<LitInt> = None;
+                let mut with: Option<Path> = None;
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("version") {
+                        from_version = Some(parse_cursor_version(nested.value()?.parse()?)?);
+                        Ok(())
+                    } else if nested.path.is_ident("with") {
+                        with = Some(nested.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(nested
+                            .error("unsupported upgrade attribute, expected `version` or `with`"))
                     }
+                })?;
+                match (from_version, with) {
+                    (Some(from_version), Some(with)) => {
+                        upgraders.push((from_version, with));
+                        Ok(())
+                    }
+                    _ => Err(meta.error("upgrade requires both `version = N` and `with = path`")),
                 }
+            } else {
+                Err(meta.error(
+                    "unsupported cursor attribute, expected `tag`, `name`, `version`, `upgrade`, `codec` or `default`",
+                ))
+            }
+        })?;
+    }
+    let Some(tag) = tag else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "RelayCursor requires a tag: #[cursor(tag = \"...\")]",
+        ));
+    };
+
+    let version_const = version.map(|version| quote! { const VERSION: u32 = #version; });
+    let upgraders_fn = (!upgraders.is_empty()).then(|| {
+        let (versions, paths): (Vec<_>, Vec<_>) = upgraders.into_iter().unzip();
+        quote! {
+            fn upgraders() -> juniper_relay_helpers::CursorUpgraders<Self::CursorType> {
+                juniper_relay_helpers::CursorUpgraders::new()
+                    #(.register(#versions, #paths))*
+            }
+        }
+    });
+
+    let codec_fn = codec.map(|codec| {
+        quote! {
+            fn codec() -> &'static juniper_relay_helpers::CursorCodec {
+                &#codec
+            }
+        }
+    });
+
+    let struct_name = &input.ident;
+    let scalar_alias = format_ident!("__{}RelayCursorScalar", struct_name);
+    let field_count = data.fields.len();
+
+    // Each field is one segment of the raw string, in declaration order.
+    let accessors = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => {
+                let idx = syn::Index::from(idx);
+                quote! { #idx }
             }
+        })
+        .collect::<Vec<_>>();
+    let parsed_fields = data.fields.iter().enumerate().map(|(idx, field)| {
+        let ty = &field.ty;
+        let part_idx = idx + 1;
+        quote! {
+            juniper_relay_helpers::unescape_cursor_segment(parts[#part_idx])?
+                .parse::<#ty>()
+                .map_err(|_| juniper_relay_helpers::CursorError::InvalidCursor)?
         }
-        _ => quote! {},
+    });
+    let default_fields = data
+        .fields
+        .iter()
+        .map(|_| quote! { ::std::default::Default::default() });
+
+    let (constructor, default_constructor) = match &data.fields {
+        Fields::Named(_) => (
+            quote! { Self { #(#accessors: #parsed_fields),* } },
+            quote! { Self { #(#accessors: #default_fields),* } },
+        ),
+        Fields::Unnamed(_) => (
+            quote! { Self(#(#parsed_fields),*) },
+            quote! { Self(#(#default_fields),*) },
+        ),
+        Fields::Unit => (quote! { Self }, quote! { Self }),
     };
 
-    out.into()
+    let default_impl = default.then(|| {
+        quote! {
+            impl std::default::Default for #struct_name {
+                fn default() -> Self {
+                    #default_constructor
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl juniper_relay_helpers::Cursor for #struct_name {
+            type CursorType = #struct_name;
+
+            #version_const
+
+            fn to_raw_string(&self) -> String {
+                let mut raw = String::from(#tag);
+                #(
+                    raw.push(':');
+                    raw.push_str(&juniper_relay_helpers::escape_cursor_segment(
+                        &self.#accessors.to_string(),
+                    ));
+                )*
+                raw
+            }
+
+            fn new(
+                _raw: &str,
+                parts: Vec<&str>,
+            ) -> Result<Self::CursorType, juniper_relay_helpers::CursorError> {
+                if parts.len() != #field_count + 1 || parts[0] != #tag {
+                    return Err(juniper_relay_helpers::CursorError::InvalidCursor);
+                }
+                Ok(#constructor)
+            }
+
+            #upgraders_fn
+
+            #codec_fn
+        }
+
+        impl std::fmt::Display for #struct_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", juniper_relay_helpers::Cursor::to_raw_string(self))
+            }
+        }
+
+        #default_impl
+
+        const _: () = {
+            #[juniper::graphql_scalar]
+            #[graphql(
+                name = #scalar_name,
+                to_output_with = <#struct_name as juniper_relay_helpers::Cursor>::to_output,
+                from_input_with = <#struct_name as juniper_relay_helpers::Cursor>::from_input,
+                parse_token_with = <#struct_name as juniper_relay_helpers::Cursor>::parse_token,
+            )]
+            type #scalar_alias = #struct_name;
+        };
+    })
+}
+
+/// Parses a cursor version, which must be a non-zero `u32`.
+fn parse_cursor_version(version: LitInt) -> syn::Result<LitInt> {
+    match version.base10_parse::<u32>() {
+        Ok(0) | Err(_) => Err(syn::Error::new_spanned(
+            &version,
+            "cursor versions must be a positive u32",
+        )),
+        Ok(_) => Ok(version),
+    }
 }
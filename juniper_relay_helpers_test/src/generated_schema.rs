@@ -1,8 +1,14 @@
 #[cfg(test)]
 mod integration_tests {
     use googletest::prelude::*;
-    use juniper::{EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, RootNode};
-    use juniper_relay_helpers::{PageInfo, RelayConnection};
+    use juniper::{
+        EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, GraphQLUnion, RootNode,
+        graphql_interface,
+    };
+    use juniper_relay_helpers::{
+        Connection, OffsetCursorProvider, PageInfo, RelayConnection, RelayCursor, RelayNodeName,
+        relay_connection,
+    };
 
     // ---- Define the types ----
 
@@ -12,10 +18,164 @@ mod integration_tests {
     }
 
     #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(nullable_count)]
     pub struct Post {
         title: String,
     }
 
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(nullability = "strict", nodes_field)]
+    pub struct Comment {
+        body: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(nullability = "resilient", nodes_field)]
+    pub struct Tag {
+        label: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[graphql(name = "Author", description = "Someone who writes posts")]
+    pub struct AuthorProfile {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        connection_type = MediaPage,
+        edge_type = MediaPageEdge,
+        connection_name = "MediaPage",
+        edge_name = "MediaPageItem",
+        connection_description = "A page of media.",
+        edge_description = "A piece of media within a page."
+    )]
+    pub struct Media {
+        url: String,
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(node = User, connection_name = "TeamMemberConnection", edge_name = "TeamMemberEdge")]
+    pub struct TeamMembership {
+        /// The member's role within the team.
+        role: String,
+        #[graphql(name = "joinedAt")]
+        joined: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        connection_fields(
+            /// The combined value of every order.
+            total_amount: i32,
+            #[graphql(name = "currencies")]
+            currency_codes: Vec<String>,
+        ),
+        connection_resolvers(
+            /// The largest order in this page.
+            largest_amount: Option<i32> = largest_order_amount,
+        )
+    )]
+    pub struct Order {
+        amount: i32,
+    }
+
+    fn largest_order_amount(orders: &OrderRelayConnection) -> Option<i32> {
+        orders.edges.iter().map(|edge| edge.node.amount).max()
+    }
+
+    #[derive(Debug, GraphQLUnion, Clone, Eq, PartialEq, RelayConnection)]
+    pub enum SearchResult {
+        User(User),
+        Post(Post),
+    }
+
+    #[graphql_interface(for = [Hero, Villain])]
+    pub struct Combatant {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone)]
+    #[graphql(impl = CombatantValue)]
+    pub struct Hero {
+        name: String,
+    }
+
+    #[derive(Debug, GraphQLObject, Clone)]
+    #[graphql(impl = CombatantValue)]
+    pub struct Villain {
+        name: String,
+        menace: i32,
+    }
+
+    relay_connection! {
+        #[graphql(name = "Combatant")]
+        pub CombatantValue
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq, RelayConnection)]
+    #[relay(
+        nullability = "strict",
+        nodes_field,
+        instance(type = Versioned<User>, name = "VersionedUser"),
+        instance(type = Versioned<Post>, name = "VersionedPost"),
+        connection_fields(latest_version: i32)
+    )]
+    pub struct Versioned<T>
+    where
+        T: Clone,
+    {
+        version: i32,
+        value: T,
+    }
+
+    #[juniper::graphql_object(name = "VersionedUser")]
+    impl Versioned<User> {
+        fn version(&self) -> i32 {
+            self.version
+        }
+
+        fn value(&self) -> &User {
+            &self.value
+        }
+    }
+
+    #[juniper::graphql_object(name = "VersionedPost")]
+    impl Versioned<Post> {
+        fn version(&self) -> i32 {
+            self.version
+        }
+
+        fn value(&self) -> &Post {
+            &self.value
+        }
+    }
+
+    // Uses the library's generic `Connection<N>`, rather than generated types:
+    pub struct Region {
+        name: String,
+    }
+
+    #[juniper::graphql_object(description = "A region of the Continent")]
+    impl Region {
+        async fn name(&self) -> &str {
+            tokio::task::yield_now().await;
+            &self.name
+        }
+    }
+
+    impl RelayNodeName for Region {
+        const CONNECTION_NAME: &'static str = "RegionConnection";
+        const EDGE_NAME: &'static str = "RegionEdge";
+    }
+
+    #[derive(Debug, RelayCursor)]
+    #[cursor(tag = "post")]
+    pub struct PostCursor {
+        published_at: String,
+        id: i32,
+    }
+
     // ----- Build the query root ----
 
     struct QueryRoot;
@@ -48,17 +208,127 @@ mod integration_tests {
             })
         }
 
-        fn get_posts() -> FieldResult<PostRelayConnection> {
-            Ok(PostRelayConnection {
-                count: 0,
-                edges: vec![],
-                page_info: PageInfo {
-                    start_cursor: None,
-                    end_cursor: None,
-                    has_prev_page: false,
-                    has_next_page: false,
+        // Echoes the fields of the decoded cursor back as a post, so that tests can check them.
+        fn get_posts(after: Option<PostCursor>) -> FieldResult<PostRelayConnection> {
+            let posts = after
+                .map(|after| Post {
+                    title: format!("After {} #{}", after.published_at, after.id),
+                })
+                .into_iter()
+                .collect::<Vec<_>>();
+            Ok(PostRelayConnection::new(
+                &posts,
+                posts.len() as i32,
+                OffsetCursorProvider::new(),
+                None,
+            ))
+        }
+
+        fn get_comments() -> CommentRelayConnection {
+            let comments = ["Whee!", "Mwah"].map(|body| Comment {
+                body: body.to_owned(),
+            });
+            CommentRelayConnection::new(&comments, 2, OffsetCursorProvider::new(), None)
+        }
+
+        fn get_tags() -> TagRelayConnection {
+            let tags = [Tag {
+                label: "expedition".to_owned(),
+            }];
+            TagRelayConnection::new(&tags, 1, OffsetCursorProvider::new(), None)
+        }
+
+        fn get_team_members() -> TeamMembershipRelayConnection {
+            TeamMembershipRelayConnection::from_edges(vec![], None, PageInfo::default())
+        }
+
+        fn get_orders() -> OrderRelayConnection {
+            OrderRelayConnection {
+                total_amount: 120,
+                ..OrderRelayConnection::from_edges(vec![], Some(0), PageInfo::default())
+            }
+        }
+
+        fn search() -> SearchResultRelayConnection {
+            let results = vec![
+                SearchResult::User(User {
+                    name: "Verso".to_owned(),
+                }),
+                SearchResult::Post(Post {
+                    title: "Lumière at dusk".to_owned(),
+                }),
+            ];
+            SearchResultRelayConnection::new(&results, 2, OffsetCursorProvider::new(), None)
+        }
+
+        fn combatants() -> CombatantValueRelayConnection {
+            let combatants: Vec<CombatantValue> = vec![
+                Hero {
+                    name: "Gustave".to_owned(),
+                }
+                .into(),
+                Villain {
+                    name: "The Paintress".to_owned(),
+                    menace: 33,
+                }
+                .into(),
+            ];
+            CombatantValueRelayConnection::new(&combatants, 2, OffsetCursorProvider::new(), None)
+        }
+
+        fn user_history() -> VersionedRelayConnection<User> {
+            let versions = [1, 2].map(|version| Versioned {
+                version,
+                value: User {
+                    name: format!("Esquie v{version}"),
                 },
-            })
+            });
+            VersionedRelayConnection {
+                latest_version: 2,
+                ..VersionedRelayConnection::new(&versions, 2, OffsetCursorProvider::new(), None)
+            }
+        }
+
+        fn post_history() -> VersionedRelayConnection<Post> {
+            VersionedRelayConnection::from_edges(vec![], Some(0), PageInfo::default())
+        }
+
+        async fn post_authors() -> UserRelayConnection {
+            let posts = ["Lumière at dusk", "Gestral beach"].map(|title| Post {
+                title: title.to_owned(),
+            });
+            PostRelayConnection::new(&posts, 5, OffsetCursorProvider::new(), None)
+                .map_nodes_async(|post| async move {
+                    tokio::task::yield_now().await;
+                    User {
+                        name: format!("Author of {}", post.title),
+                    }
+                })
+                .await
+        }
+
+        fn regions() -> Connection<Region> {
+            let regions = ["Flying Waters", "Stone Wave Cliffs"].map(|name| Region {
+                name: name.to_owned(),
+            });
+            Connection::from_nodes(regions, 2, OffsetCursorProvider::new(), None)
+        }
+
+        fn get_authors() -> AuthorProfileRelayConnection {
+            AuthorProfileRelayConnection::from_edges(vec![], None, PageInfo::default())
+        }
+
+        fn get_media() -> MediaPage {
+            MediaPage::from_edges(
+                vec![MediaPageEdge {
+                    node: Media {
+                        url: "https://example.com/gommage.png".to_owned(),
+                    },
+                    cursor: None,
+                }],
+                None,
+                PageInfo::default(),
+            )
         }
     }
 
@@ -90,6 +360,356 @@ mod integration_tests {
         assert_that!(schema_sdl, contains_substring("Connection type for Post."));
     }
 
+    #[test]
+    fn default_nullability_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_substring("type UserConnection {\n  count: Int!\n")
+        );
+        assert_that!(schema_sdl, contains_substring("edges: [UserEdge!]!"));
+        assert_that!(schema_sdl, contains_substring("node: User!"));
+        assert_that!(schema_sdl, contains_substring("cursor: String\n"));
+    }
+
+    #[test]
+    fn nullable_count_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_substring("type PostConnection {\n  count: Int\n")
+        );
+    }
+
+    #[test]
+    fn strict_nullability_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("edges: [CommentEdge!]!"));
+        assert_that!(schema_sdl, contains_substring("node: Comment!"));
+        assert_that!(schema_sdl, contains_substring("cursor: String!"));
+    }
+
+    #[test]
+    fn resilient_nullability_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("edges: [TagEdge]\n"));
+        assert_that!(schema_sdl, contains_substring("node: Tag\n"));
+    }
+
+    #[test]
+    fn names_follow_renamed_node() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("type AuthorConnection"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("Connection type for Author.")
+        );
+        assert_that!(schema_sdl, contains_substring("type AuthorEdge"));
+        assert_that!(schema_sdl, contains_substring("Edge type for Author."));
+        assert_that!(schema_sdl, not(contains_substring("AuthorProfile")));
+    }
+
+    #[test]
+    fn custom_names_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("type MediaPage "));
+        assert_that!(schema_sdl, contains_substring("A page of media."));
+        assert_that!(schema_sdl, contains_substring("edges: [MediaPageItem!]!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("A piece of media within a page.")
+        );
+        assert_that!(schema_sdl, not(contains_substring("MediaConnection")));
+    }
+
+    #[test]
+    fn edge_data_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("type TeamMemberEdge"));
+        assert_that!(schema_sdl, contains_substring("node: User!"));
+        assert_that!(schema_sdl, contains_substring("role: String!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("The member's role within the team.")
+        );
+        assert_that!(schema_sdl, contains_substring("joinedAt: String!"));
+        assert_that!(schema_sdl, contains_substring("edges: [TeamMemberEdge!]!"));
+    }
+
+    #[test]
+    fn connection_fields_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("totalAmount: Int!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("The combined value of every order.")
+        );
+        assert_that!(schema_sdl, contains_substring("currencies: [String!]!"));
+        assert_that!(schema_sdl, contains_substring("largestAmount: Int"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("The largest order in this page.")
+        );
+    }
+
+    #[test]
+    fn connection_resolvers_resolve() {
+        let (result, errors) = juniper::execute_sync(
+            "{ getOrders { totalAmount largestAmount } }",
+            None,
+            &build_schema(),
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "getOrders": { "totalAmount": 120, "largestAmount": null },
+            })
+        );
+    }
+
+    #[test]
+    fn union_connection_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_substring("union SearchResult = User | Post")
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("type SearchResultConnection")
+        );
+        assert_that!(schema_sdl, contains_substring("node: SearchResult!"));
+    }
+
+    #[test]
+    fn interface_connection_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("interface Combatant"));
+        assert_that!(schema_sdl, contains_substring("type CombatantConnection"));
+        assert_that!(schema_sdl, contains_substring("Edge type for Combatant."));
+        assert_that!(schema_sdl, contains_substring("node: Combatant!"));
+    }
+
+    #[test]
+    fn mixed_node_types_resolve() {
+        let schema = build_schema();
+        let (result, errors) = juniper::execute_sync(
+            "{
+                search { edges { node { __typename ... on User { name } ... on Post { title } } } }
+                combatants { edges { node { __typename name ... on Villain { menace } } } }
+            }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "search": { "edges": [
+                    { "node": { "__typename": "User", "name": "Verso" } },
+                    { "node": { "__typename": "Post", "title": "Lumière at dusk" } },
+                ] },
+                "combatants": { "edges": [
+                    { "node": { "__typename": "Hero", "name": "Gustave" } },
+                    { "node": { "__typename": "Villain", "name": "The Paintress", "menace": 33 } },
+                ] },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn mapped_nodes_resolve() {
+        let schema = build_schema();
+        let (result, errors) = juniper::execute(
+            "{ postAuthors { count edges { cursor node { name } } } }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "postAuthors": {
+                    "count": 5,
+                    "edges": [
+                        { "cursor": "b2Zmc2V0OjA=", "node": { "name": "Author of Lumière at dusk" } },
+                        { "cursor": "b2Zmc2V0OjE=", "node": { "name": "Author of Gestral beach" } },
+                    ],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn generic_connection_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("regions: RegionConnection!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("Connection type for Region.")
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring(
+                "type RegionConnection {\n  count: Int!\n  edges: [RegionEdge!]!\n  pageInfo: PageInfo!\n}"
+            )
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("type RegionEdge {\n  node: Region!\n  cursor: String\n}")
+        );
+    }
+
+    #[tokio::test]
+    async fn generic_connection_resolves() {
+        let schema = build_schema();
+        let (result, errors) = juniper::execute(
+            "{ regions { count edges { cursor node { name } } pageInfo { hasNextPage } } }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .await
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "regions": {
+                    "count": 2,
+                    "edges": [
+                        { "cursor": "b2Zmc2V0OjA=", "node": { "name": "Flying Waters" } },
+                        { "cursor": "b2Zmc2V0OjE=", "node": { "name": "Stone Wave Cliffs" } },
+                    ],
+                    "pageInfo": { "hasNextPage": false },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn generic_instances_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(
+            schema_sdl,
+            contains_substring("type VersionedUserConnection")
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("Connection type for VersionedUser.")
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("edges: [VersionedUserEdge!]!")
+        );
+        assert_that!(schema_sdl, contains_substring("node: VersionedUser!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("type VersionedPostConnection")
+        );
+        assert_that!(schema_sdl, contains_substring("latestVersion: Int!"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("userHistory: VersionedUserConnection!")
+        );
+        assert_that!(
+            schema_sdl,
+            contains_substring("postHistory: VersionedPostConnection!")
+        );
+    }
+
+    #[test]
+    fn generic_instances_resolve() {
+        let schema = build_schema();
+        let (result, errors) = juniper::execute_sync(
+            "{ userHistory { count latestVersion edges { node { version value { name } } } } }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "userHistory": {
+                    "count": 2,
+                    "latestVersion": 2,
+                    "edges": [
+                        { "node": { "version": 1, "value": { "name": "Esquie v1" } } },
+                        { "node": { "version": 2, "value": { "name": "Esquie v2" } } },
+                    ],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn nodes_field_generated() {
+        let schema_sdl = build_schema().as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("nodes: [Comment!]!"));
+        assert_that!(schema_sdl, contains_substring("nodes: [Tag]"));
+        assert_that!(schema_sdl, contains_substring("nodes: [VersionedUser!]!"));
+        assert_that!(schema_sdl, not(contains_substring("nodes: [User")));
+    }
+
+    #[test]
+    fn nodes_field_resolves() {
+        let schema = build_schema();
+        let (result, errors) = juniper::execute_sync(
+            "{
+                getComments { count nodes { body } edges { node { body } } }
+                getTags { nodes { label } }
+                userHistory { nodes { version } }
+            }",
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "getComments": {
+                    "count": 2,
+                    "nodes": [{ "body": "Whee!" }, { "body": "Mwah" }],
+                    "edges": [{ "node": { "body": "Whee!" } }, { "node": { "body": "Mwah" } }],
+                },
+                "getTags": { "nodes": [{ "label": "expedition" }] },
+                "userHistory": { "nodes": [{ "version": 1 }, { "version": 2 }] },
+            })
+        );
+    }
+
     #[test]
     fn edge_info_generated() {
         let schema_document = build_schema();
@@ -102,6 +722,49 @@ mod integration_tests {
         assert_that!(schema_sdl, contains_substring("Edge type for Post."));
     }
 
+    #[test]
+    fn derived_cursor_scalar_generated() {
+        let schema_document = build_schema();
+        let schema_sdl = schema_document.as_sdl();
+
+        assert_that!(schema_sdl, contains_substring("scalar PostCursor"));
+        assert_that!(
+            schema_sdl,
+            contains_substring("getPosts(after: PostCursor)")
+        );
+    }
+
+    #[test]
+    fn derived_cursor_argument_decoded() {
+        use juniper_relay_helpers::Cursor;
+
+        let after = PostCursor {
+            published_at: "2025-04-24T10:30:00Z".to_owned(),
+            id: 12,
+        };
+        let schema = build_schema();
+        let (result, errors) = juniper::execute_sync(
+            &format!(
+                "{{ getPosts(after: \"{}\") {{ edges {{ node {{ title }} }} }} }}",
+                after.to_encoded_string()
+            ),
+            None,
+            &schema,
+            &juniper::Variables::new(),
+            &(),
+        )
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(
+            result,
+            juniper::graphql_value!({
+                "getPosts": {
+                    "edges": [{ "node": { "title": "After 2025-04-24T10:30:00Z #12" } }],
+                },
+            })
+        );
+    }
+
     #[test]
     fn pagination_info_generated() {
         let schema_document = build_schema();
@@ -109,5 +772,125 @@ mod integration_tests {
 
         assert_that!(schema_sdl, contains_substring("type PageInfo"));
         assert_that!(schema_sdl, contains_substring("Pagination information"));
+        assert_that!(schema_sdl, contains_substring("hasPreviousPage: Boolean!"));
+        #[cfg(not(feature = "has-prev-page-alias"))]
+        assert_that!(schema_sdl, not(contains_substring("hasPrevPage")));
+        #[cfg(feature = "has-prev-page-alias")]
+        assert_that!(
+            schema_sdl,
+            contains_substring(
+                "hasPrevPage: Boolean! @deprecated(reason: \"Use `hasPreviousPage`.\")"
+            )
+        );
+    }
+
+    // ---- Resolvers with access to the context ----
+
+    mod context_resolvers {
+        use googletest::prelude::*;
+        use juniper::{EmptyMutation, EmptySubscription, GraphQLObject, RootNode};
+        use juniper_relay_helpers::{OffsetCursorProvider, RelayConnection};
+
+        pub struct Library {
+            hidden_titles: Vec<String>,
+            borrowers: Vec<(String, String)>,
+        }
+        impl juniper::Context for Library {}
+
+        #[derive(Debug, GraphQLObject, Clone, Eq, PartialEq, RelayConnection)]
+        #[relay(
+            context = Library,
+            connection_resolvers(
+                /// How many of the books the viewer is allowed to see.
+                visible_count: i32 = BookRelayConnection::count_visible,
+            ),
+            edge_resolvers(
+                #[graphql(name = "borrowedBy")]
+                async borrower: Option<String> = load_borrower,
+            )
+        )]
+        pub struct Book {
+            title: String,
+        }
+
+        impl BookRelayConnection {
+            fn count_visible(&self, library: &Library) -> i32 {
+                self.edges
+                    .iter()
+                    .filter(|edge| !library.hidden_titles.contains(&edge.node.title))
+                    .count() as i32
+            }
+        }
+
+        async fn load_borrower(edge: &BookRelayEdge, library: &Library) -> Option<String> {
+            tokio::task::yield_now().await;
+            library
+                .borrowers
+                .iter()
+                .find(|(title, _)| *title == edge.node.title)
+                .map(|(_, borrower)| borrower.clone())
+        }
+
+        struct LibraryQuery;
+
+        #[juniper::graphql_object(context = Library)]
+        impl LibraryQuery {
+            fn books() -> BookRelayConnection {
+                let books = ["The Gommage", "Paintress Journals"].map(|title| Book {
+                    title: title.to_owned(),
+                });
+                BookRelayConnection::new(&books, 2, OffsetCursorProvider::new(), None)
+            }
+        }
+
+        type LibrarySchema =
+            RootNode<LibraryQuery, EmptyMutation<Library>, EmptySubscription<Library>>;
+
+        fn build_schema() -> LibrarySchema {
+            LibrarySchema::new(LibraryQuery, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        #[test]
+        fn context_resolvers_generated() {
+            let schema_sdl = build_schema().as_sdl();
+
+            assert_that!(
+                schema_sdl,
+                contains_substring("How many of the books the viewer is allowed to see.")
+            );
+            assert_that!(schema_sdl, contains_substring("visibleCount: Int!"));
+            assert_that!(schema_sdl, contains_substring("borrowedBy: String"));
+        }
+
+        #[tokio::test]
+        async fn context_resolvers_resolve() {
+            let library = Library {
+                hidden_titles: vec!["Paintress Journals".to_owned()],
+                borrowers: vec![("The Gommage".to_owned(), "Maelle".to_owned())],
+            };
+            let (result, errors) = juniper::execute(
+                "{ books { count visibleCount edges { node { title } borrowedBy } } }",
+                None,
+                &build_schema(),
+                &juniper::Variables::new(),
+                &library,
+            )
+            .await
+            .unwrap();
+            assert!(errors.is_empty());
+            assert_eq!(
+                result,
+                juniper::graphql_value!({
+                    "books": {
+                        "count": 2,
+                        "visibleCount": 1,
+                        "edges": [
+                            { "node": { "title": "The Gommage" }, "borrowedBy": "Maelle" },
+                            { "node": { "title": "Paintress Journals" }, "borrowedBy": null },
+                        ],
+                    },
+                })
+            );
+        }
     }
 }
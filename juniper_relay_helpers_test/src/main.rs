@@ -110,7 +110,7 @@ mod integration_tests {
                         startCursor
                         endCursor
                         hasNextPage
-                        hasPrevPage
+                        hasPreviousPage
                     }
                 }
             }";
@@ -130,20 +130,61 @@ mod integration_tests {
                         startCursor
                         endCursor
                         hasNextPage
-                        hasPrevPage
+                        hasPreviousPage
+                    }
+                }
+            }";
+
+    const PAGINATED_CHARACTERS_QUERY: &str = r"
+            query Characters($first: Int, $after: StringCursor) {
+                characters(first: $first, after: $after) {
+                    edges {
+                        node {
+                            name
+                        }
+                        cursor
+                    }
+                    pageInfo {
+                        startCursor
+                        endCursor
+                        hasNextPage
+                        hasPreviousPage
+                    }
+                }
+            }";
+
+    const PAGINATED_LOCATIONS_QUERY: &str = r"
+            query Locations($first: Int, $after: OffsetCursor, $last: Int, $before: OffsetCursor) {
+                locations(first: $first, after: $after, last: $last, before: $before) {
+                    count
+                    edges {
+                        node {
+                            id
+                            name
+                        }
+                        cursor
+                    }
+                    pageInfo {
+                        startCursor
+                        endCursor
+                        hasNextPage
+                        hasPreviousPage
                     }
                 }
             }";
 
     mod connection_tests {
         use crate::build_app;
-        use crate::integration_tests::{ALL_CHARACTERS_QUERY, ALL_LOCATIONS_QUERY};
+        use crate::integration_tests::{
+            ALL_CHARACTERS_QUERY, ALL_LOCATIONS_QUERY, PAGINATED_CHARACTERS_QUERY,
+            PAGINATED_LOCATIONS_QUERY,
+        };
         use crate::schema::{EntityType, get_character_test_data, get_location_test_data};
         use axum_test::TestServer;
         use axum_test::expect_json;
         use axum_test::expect_json::__private::serde_json;
         use axum_test::expect_json::__private::serde_json::json;
-        use juniper_relay_helpers::RelayIdentifier;
+        use juniper_relay_helpers::{Cursor, RelayIdentifier, StringCursor};
         use serde::{Deserialize, Serialize};
 
         #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -184,7 +225,7 @@ mod integration_tests {
                             })),
                         "pageInfo": expect_json::object().contains(json!({
                             "hasNextPage": false,
-                            "hasPrevPage": false
+                            "hasPreviousPage": false
                         }))
                     }))
                 }))
@@ -223,13 +264,154 @@ mod integration_tests {
                             })),
                         "pageInfo": expect_json::object().contains(json!({
                             "hasNextPage": false,
-                            "hasPrevPage": false
+                            "hasPreviousPage": false
                         }))
                     }))
                 }))
             }))
         }
 
+        #[tokio::test]
+        async fn test_character_connections_paginated() {
+            let app = build_app();
+            let server = TestServer::new(app).unwrap();
+            let character_test_data = get_character_test_data();
+
+            let response = server
+                .post("/graphql")
+                .json(&GraphQLPayload {
+                    query: PAGINATED_CHARACTERS_QUERY.to_string(),
+                    variables: Some(json!({ "first": 2 })),
+                })
+                .await;
+
+            response.assert_status_ok();
+            response.assert_json(&json!({
+                "data": {
+                    "characters": {
+                        "edges": [
+                            {
+                                "node": { "name": character_test_data[0].name },
+                                "cursor": StringCursor::new(character_test_data[0].id.to_string()).to_encoded_string(),
+                            },
+                            {
+                                "node": { "name": character_test_data[1].name },
+                                "cursor": StringCursor::new(character_test_data[1].id.to_string()).to_encoded_string(),
+                            },
+                        ],
+                        "pageInfo": expect_json::object().contains(json!({
+                            "hasNextPage": true,
+                            "hasPreviousPage": false
+                        }))
+                    }
+                }
+            }));
+
+            // Carry on from the ID-based cursor of the last character:
+            let end_cursor = response.json::<serde_json::Value>()["data"]["characters"]["pageInfo"]
+                ["endCursor"]
+                .clone();
+            let response = server
+                .post("/graphql")
+                .json(&GraphQLPayload {
+                    query: PAGINATED_CHARACTERS_QUERY.to_string(),
+                    variables: Some(json!({ "first": 2, "after": end_cursor })),
+                })
+                .await;
+
+            response.assert_status_ok();
+            response.assert_json(&json!({
+                "data": expect_json::object().contains(json!({
+                    "characters": expect_json::object().contains(json!({
+                        "edges": [
+                            expect_json::object().contains(json!({
+                                "node": { "name": character_test_data[2].name },
+                            })),
+                            expect_json::object().contains(json!({
+                                "node": { "name": character_test_data[3].name },
+                            })),
+                        ],
+                        "pageInfo": expect_json::object().contains(json!({
+                            "hasNextPage": true,
+                            "hasPreviousPage": true
+                        }))
+                    }))
+                }))
+            }));
+        }
+
+        #[tokio::test]
+        async fn test_location_connections_backwards() {
+            let app = build_app();
+            let server = TestServer::new(app).unwrap();
+            let location_test_data = get_location_test_data();
+
+            // Last page of the set:
+            let response = server
+                .post("/graphql")
+                .json(&GraphQLPayload {
+                    query: PAGINATED_LOCATIONS_QUERY.to_string(),
+                    variables: Some(json!({ "last": 2 })),
+                })
+                .await;
+
+            response.assert_status_ok();
+            response.assert_json(&json!({
+                "data": expect_json::object().contains(json!({
+                    "locations": expect_json::object().contains(json!({
+                        "count": location_test_data.len(),
+                        "edges": [
+                            expect_json::object().contains(json!({
+                                "node": expect_json::object().contains(json!({
+                                    "name": location_test_data[1].name,
+                                })),
+                            })),
+                            expect_json::object().contains(json!({
+                                "node": expect_json::object().contains(json!({
+                                    "name": location_test_data[2].name,
+                                })),
+                            })),
+                        ],
+                        "pageInfo": expect_json::object().contains(json!({
+                            "hasNextPage": false,
+                            "hasPreviousPage": true
+                        }))
+                    }))
+                }))
+            }));
+
+            // Then the page before that, which only has the one item left in it:
+            let start_cursor = response.json::<serde_json::Value>()["data"]["locations"]
+                ["pageInfo"]["startCursor"]
+                .clone();
+            let response = server
+                .post("/graphql")
+                .json(&GraphQLPayload {
+                    query: PAGINATED_LOCATIONS_QUERY.to_string(),
+                    variables: Some(json!({ "last": 2, "before": start_cursor })),
+                })
+                .await;
+
+            response.assert_status_ok();
+            response.assert_json(&json!({
+                "data": expect_json::object().contains(json!({
+                    "locations": expect_json::object().contains(json!({
+                        "edges": [
+                            expect_json::object().contains(json!({
+                                "node": expect_json::object().contains(json!({
+                                    "name": location_test_data[0].name,
+                                })),
+                            })),
+                        ],
+                        "pageInfo": expect_json::object().contains(json!({
+                            "hasNextPage": true,
+                            "hasPreviousPage": false
+                        }))
+                    }))
+                }))
+            }));
+        }
+
         #[tokio::test]
         async fn test_relay_identifier_uuid() {
             let app = build_app();
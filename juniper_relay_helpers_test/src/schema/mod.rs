@@ -1,12 +1,10 @@
-pub use crate::schema::character::{
-    Character, CharacterRelayConnection, CharacterRelayEdge, CharacterRow,
-};
+pub use crate::schema::character::{Character, CharacterRelayConnection, CharacterRow};
 pub use crate::schema::identifiers::EntityType;
 pub use crate::schema::location::{Location, LocationRelayConnection, LocationRow};
 use juniper::{EmptyMutation, EmptySubscription, FieldResult, RootNode};
 use juniper_relay_helpers::{
-    OffsetCursor, OffsetCursorProvider, PageInfo, PageRequest, RelayConnection, RelayEdge,
-    RelayIdentifier,
+    NodeCursorProvider, OffsetCursor, PageRequest, RelayConnection, RelayIdentifier, StringCursor,
+    connection_from_array,
 };
 
 mod character;
@@ -32,65 +30,62 @@ pub struct QueryRoot;
 #[juniper::graphql_object(context = Context)]
 impl QueryRoot {
     /// Queries for all characters in the "database"
-    /// This method shows how you can manually build up the resulting structs without using
-    /// cursor providers or any of the other fancy stuff.
-    async fn characters(ctx: &Context) -> FieldResult<CharacterRelayConnection> {
-        Ok(CharacterRelayConnection {
-            count: ctx.characters.len() as i32,
-            edges: ctx
-                .characters
+    /// This method builds each cursor from the character's ID using a `NodeCursorProvider`, rather
+    /// than its position, so cursors stay valid even if characters are added or removed between pages.
+    async fn characters(
+        first: Option<i32>,
+        after: Option<StringCursor>,
+        ctx: &Context,
+    ) -> FieldResult<CharacterRelayConnection> {
+        let start = after.as_ref().map_or(0, |after| {
+            ctx.characters
                 .iter()
-                .enumerate()
-                .map(|(idx, row)| {
-                    CharacterRelayEdge::new(
-                        Character {
-                            id: RelayIdentifier::new(row.id, EntityType::Character),
-                            name: row.name.clone(),
-                        },
-                        OffsetCursor {
-                            offset: idx as i32,
-                            first: Some(10),
-                        },
-                    )
-                })
-                .collect(),
-            page_info: PageInfo {
-                has_next_page: false,
-                has_prev_page: false,
-                start_cursor: None,
-                end_cursor: None,
-            },
-        })
+                .position(|row| row.id.to_string() == after.value)
+                .map_or(0, |idx| idx + 1)
+        });
+
+        let nodes = ctx
+            .characters
+            .iter()
+            .skip(start)
+            .take(first.map_or(usize::MAX, |first| first as usize))
+            .map(|row| Character {
+                id: RelayIdentifier::new(row.id, EntityType::Character),
+                name: row.name.clone(),
+            })
+            .collect::<Vec<Character>>();
+
+        Ok(CharacterRelayConnection::new(
+            &nodes,
+            ctx.characters.len() as i32,
+            NodeCursorProvider::new(|character: &Character| {
+                StringCursor::new(character.id.id.to_string())
+            }),
+            Some(PageRequest::new(first, after)),
+        ))
     }
 
     /// Queries for all locations in the "database"
     /// This method makes use of cursor providers and the shortcut methods to show how much you can
-    /// hand off to the library:
+    /// hand off to the library. It supports paginating both forwards (`first` / `after`) and
+    /// backwards (`last` / `before`).
     async fn locations(
         first: Option<i32>,
         after: Option<OffsetCursor>,
+        last: Option<i32>,
+        before: Option<OffsetCursor>,
         ctx: &Context,
     ) -> FieldResult<LocationRelayConnection> {
-        let mut nodes = ctx
+        let nodes = ctx
             .locations
             .iter()
             .map(|row| Location::from(row.clone()))
             .collect::<Vec<Location>>();
 
-        if let Some(after) = &after {
-            nodes = nodes.split_off(after.offset as usize + 1);
-        }
-
-        if let Some(first) = first {
-            nodes.truncate(first as usize);
-        }
-
-        Ok(LocationRelayConnection::new(
+        Ok(connection_from_array(
             &nodes,
-            ctx.locations.len() as i32,
-            OffsetCursorProvider::new(),
-            Some(PageRequest::new(first, after)),
-        ))
+            &PageRequest::from_arguments(first, after, last, before),
+        )?)
     }
 }
 